@@ -0,0 +1,224 @@
+/*
+ * Runs any (or all) of the 2021 Advent-of-Code puzzles from
+ * a single binary:
+ *
+ *   $ aoc run --day 5 data/vents.txt
+ *   $ aoc run --all
+ *   $ aoc inputs list
+ */
+use std::path::{Path, PathBuf};
+
+use clap::{App, AppSettings, Arg, ArgMatches};
+
+use util::{Format, InputStatus, InputStore, Puzzle, RunOptions};
+
+/* Every puzzle with a solver, in day order. */
+const PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<sonarsweep::SonarSweep>(),
+    Puzzle::of::<dive::SubmarineCommands>(),
+    Puzzle::of::<bindiag::BinaryDiagnostic>(),
+    Puzzle::of::<giantsquid::BingoGame>(),
+    Puzzle::of::<vents::VentSurvey>(),
+    Puzzle::of::<lanternfish::Lanternfish>(),
+    Puzzle::of::<whales::SubPositions>(),
+    Puzzle::of::<smokebasin::HeightMap>(),
+];
+
+fn find_puzzle(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.day == day)
+}
+
+fn run_puzzle(puzzle: &Puzzle, inputs: &[PathBuf], options: &RunOptions) -> bool {
+    if options.format == Format::Text {
+        println!("Day {}: {}", puzzle.day, puzzle.title);
+    }
+    util::run_inputs(puzzle, inputs, options)
+}
+
+/* Runs a puzzle on the input from the store, fetching it if need be. */
+fn run_stored(puzzle: &Puzzle, store: &mut InputStore, options: &RunOptions) -> bool {
+    match store.resolve(puzzle) {
+        Ok(input) => run_puzzle(puzzle, &[input], options),
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        }
+    }
+}
+
+/* Looks up the puzzle for --day, exiting if there isn't one. */
+fn day_puzzle(argmatches: &ArgMatches) -> &'static Puzzle {
+    let day = argmatches.value_of("day").unwrap();
+    match day.parse::<u32>().ok().and_then(find_puzzle) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("No solver for day {}!", day);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(argmatches: &ArgMatches) {
+    let mut store = util::input_store(argmatches);
+    let options = util::run_options(argmatches);
+
+    let ok = if argmatches.is_present("all") {
+        let mut ok = true;
+        for puzzle in PUZZLES {
+            ok &= run_stored(puzzle, &mut store, &options);
+        }
+        ok
+    } else {
+        let puzzle = day_puzzle(argmatches);
+        match argmatches.values_of("input") {
+            Some(inputs) => {
+                let inputs: Vec<PathBuf> = inputs.map(PathBuf::from).collect();
+                run_puzzle(puzzle, &inputs, &options)
+            },
+            None => run_stored(puzzle, &mut store, &options),
+        }
+    };
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+/* Prints the state of every puzzle's input; with check, only the
+ * ones with problems, returning false if any are corrupt or stale.
+ */
+fn list_inputs(store: &InputStore, check: bool) -> bool {
+    let mut ok = true;
+    if !check {
+        println!("{:>3}  {:<12} {:<16}  status", "day", "name", "checksum");
+    }
+    for puzzle in PUZZLES {
+        let (checksum, status) = match store.checksum(puzzle).and_then(|c| Ok((c, store.status(puzzle)?))) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}", e);
+                ok = false;
+                continue;
+            }
+        };
+        let problem = matches!(status, InputStatus::Corrupt { .. } | InputStatus::Stale { .. });
+        ok &= !problem;
+        if check && !problem {
+            continue;
+        }
+        let checksum = checksum.map_or_else(|| "-".to_string(), |c| format!("{:016x}", c));
+        println!("{:>3}  {:<12} {:<16}  {}", puzzle.day, puzzle.name, checksum, status);
+    }
+    ok
+}
+
+fn inputs(argmatches: &ArgMatches) {
+    let (command, argmatches) = argmatches.subcommand().unwrap();
+    let mut store = util::input_store(argmatches);
+    let ok = match command {
+        "list" => list_inputs(&store, false),
+        "check" => list_inputs(&store, true),
+        "import" => {
+            let puzzle = day_puzzle(argmatches);
+            let file = Path::new(argmatches.value_of("file").unwrap());
+            match store.import(puzzle, file) {
+                Ok(checksum) => {
+                    println!("Imported day {} input into {} ({:016x})",
+                        puzzle.day, store.path(puzzle).display(), checksum);
+                    true
+                },
+                Err(e) => {
+                    eprintln!("{}", e);
+                    false
+                }
+            }
+        },
+        "fetch" => {
+            let puzzles = if argmatches.is_present("all") {
+                PUZZLES.iter().collect()
+            } else {
+                vec![day_puzzle(argmatches)]
+            };
+            let mut ok = true;
+            for puzzle in puzzles {
+                match store.fetch(puzzle) {
+                    Ok(checksum) => println!("Fetched day {} input into {} ({:016x})",
+                        puzzle.day, store.path(puzzle).display(), checksum),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        ok = false;
+                    }
+                }
+            }
+            ok
+        },
+        _ => unreachable!(),
+    };
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+fn day_arg<'help>() -> Arg<'help> {
+    Arg::new("day")
+        .about("Day of the puzzle")
+        .short('d')
+        .long("day")
+        .takes_value(true)
+}
+
+fn main() {
+    let argmatches = App::new("aoc")
+        .version("0.1")
+        .author("Jack Bradach <jack@bradach.net>")
+        .about("Advent of Code 2021 - all puzzles")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(App::new("run")
+            .about("Solve one day's puzzle, or all of them")
+            .arg(day_arg()
+                .required_unless_present("all"))
+            .arg(Arg::new("all")
+                .about("Solve every puzzle using the inputs in the data directory")
+                .long("all")
+                .conflicts_with_all(&["day", "input"]))
+            .args(util::store_args())
+            .args(util::run_args())
+            .args(PUZZLES.iter().flat_map(|puzzle| (puzzle.args)()))
+            .arg(Arg::new("input")
+                .about("Input datasets from website; - reads stdin (defaults to the data directory)")
+                .index(1)
+                .multiple_values(true)))
+        .subcommand(App::new("inputs")
+            .about("Manage the local store of puzzle inputs")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(App::new("list")
+                .about("List each puzzle's input with its checksum and status")
+                .args(util::store_args()))
+            .subcommand(App::new("check")
+                .about("Report inputs that are corrupt or stale, exiting non-zero if there are any")
+                .args(util::store_args()))
+            .subcommand(App::new("import")
+                .about("Copy an input into the store and record its checksum")
+                .args(util::store_args())
+                .arg(day_arg()
+                    .required(true))
+                .arg(Arg::new("file")
+                    .about("Input to import; - reads stdin")
+                    .index(1)
+                    .required(true)))
+            .subcommand(App::new("fetch")
+                .about("Import inputs from the --source directory")
+                .args(util::store_args())
+                .arg(day_arg()
+                    .required_unless_present("all"))
+                .arg(Arg::new("all")
+                    .about("Fetch every puzzle's input")
+                    .long("all")
+                    .conflicts_with("day"))))
+        .get_matches();
+
+    match argmatches.subcommand() {
+        Some(("run", argmatches)) => run(argmatches),
+        Some(("inputs", argmatches)) => inputs(argmatches),
+        _ => unreachable!(),
+    }
+}
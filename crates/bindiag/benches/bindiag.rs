@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use bindiag::BinaryDiagnostic;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is 1000 12-bit readings.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    (0..1000 * scale)
+        .map(|_| format!("{:012b}", rng.below(1 << 12)))
+        .collect()
+}
+
+fn parse(input: &[String]) -> BinaryDiagnostic {
+    BinaryDiagnostic::from_vecstring(input).unwrap()
+}
+
+fn bench_bindiag(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bindiag");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_bindiag);
+criterion_main!(benches);
@@ -0,0 +1,219 @@
+/*
+ * https://adventofcode.com/2021/day/3
+ */
+use std::io::BufRead;
+
+use util::{ParseError, Solver};
+
+#[derive(Clone, Debug)]
+pub struct BinaryDiagnostic {
+    report_width: usize,
+    report: Vec<u32>,
+}
+
+impl BinaryDiagnostic {
+    pub fn from_reader<R: BufRead>(input: R) -> Result<BinaryDiagnostic, ParseError> {
+        let raw_report = util::read_lines(input)?;
+        BinaryDiagnostic::from_vecstring(&raw_report)
+    }
+
+    pub fn from_vecstring(raw_report: &[String]) -> Result<BinaryDiagnostic, ParseError> {
+        let report_width = match raw_report.first() {
+            Some(line) => line.len(),
+            None => return Err(ParseError::new("no diagnostic report in input")),
+        };
+        if report_width == 0 || report_width > 32 {
+            return Err(ParseError::new("report values must be 1 to 32 bits wide")
+                .on_line(1, &raw_report[0]));
+        }
+        let report = util::parse_lines(raw_report, |s| {
+            if s.len() != report_width {
+                return Err(ParseError::new(format!("expected {} bits, found {}", report_width, s.len()))
+                    .at_column(1));
+            }
+            match s.find(|c| c != '0' && c != '1') {
+                Some(i) => Err(ParseError::new("expected a binary digit").at_column(i + 1)),
+                None => Ok(u32::from_str_radix(s, 2).unwrap()),
+            }
+        })?;
+        Ok(BinaryDiagnostic {
+            report_width,
+            report,
+        })
+    }
+
+    pub fn episilon(&self) -> u32 {
+        const MAX: usize = 32;
+        let mut density_ones: [u32; MAX] = [0; MAX];
+        let mut density_zeroes: [u32; MAX] = [0; MAX];
+        for i in 0..self.report_width {
+            for v in &self.report {
+                if v & (1 << i) == 0 {
+                    density_zeroes[i] += 1;
+                } else {
+                    density_ones[i] += 1;
+                }
+            }
+        }
+        let mut episilon: u32 = 0;
+        for i in 0..self.report_width {
+            if density_zeroes[i] > density_ones[i] {
+                episilon |= 1 << i;
+            }
+        }
+        episilon
+    }
+
+    pub fn gamma(&self) -> u32 {
+        const REPORT_WIDTH: usize = 32;
+        let mut density_ones: [u32; REPORT_WIDTH] = [0; REPORT_WIDTH];
+        let mut density_zeroes: [u32; REPORT_WIDTH] = [0; REPORT_WIDTH];
+        for i in 0..REPORT_WIDTH {
+            for v in &self.report {
+                if v & (1 << i) != 0 {
+                    density_ones[i] += 1;
+                } else {
+                    density_zeroes[i] += 1;
+                }
+            }
+        }
+        let mut gamma = 0;
+        for i in 0..REPORT_WIDTH {
+            if density_ones[i] > density_zeroes[i] {
+                gamma |= 1 << i;
+            }
+        }
+        gamma
+    }
+
+    pub fn oxygen(&self) -> u32 {
+        const MAX: usize = 32;
+        let mut density_ones: [u32; MAX] = [0; MAX];
+        let mut density_zeroes: [u32; MAX] = [0; MAX];
+        /* Need to scan through each bit, find the most common,
+         * and then use it to prune the rest of the vector. */
+        let mut diag = self.report.clone();
+        for i in (0..self.report_width).rev() {
+            for v in &diag {
+                if v & (1 << i) == 0 {
+                    density_zeroes[i] += 1;
+                } else {
+                    density_ones[i] += 1;
+                }
+            }
+            let polarity = if density_ones[i] >= density_zeroes[i] { 1 } else { 0 };
+            diag = diag
+                .iter()
+                .filter_map(|&v| {
+                    if ((v & (1 << i)) >> i) == polarity {
+                        Some(v)
+                    } else { 
+                        None 
+                    }
+                }).collect::<Vec<_>>();
+            if diag.len() == 1 {
+                break;
+            }
+        }
+        diag.pop().unwrap()
+    }
+
+    pub fn co2(&self) -> u32 {
+        const MAX: usize = 32;
+        let mut density_ones: [u32; MAX] = [0; MAX];
+        let mut density_zeroes: [u32; MAX] = [0; MAX];
+        /* Need to scan through each bit, find the most common,
+         * and then use it to prune the rest of the vector. */
+        let mut diag = self.report.clone();
+        for i in (0..self.report_width).rev() {
+            for v in &diag {
+                if v & (1 << i) == 0 {
+                    density_zeroes[i] += 1;
+                } else {
+                    density_ones[i] += 1;
+                }
+            }
+            let polarity = if density_zeroes[i] <= density_ones[i] { 0 } else { 1 };
+            diag = diag
+                .iter()
+                .filter_map(|&v| {
+                    if ((v & (1 << i)) >> i) == polarity {
+                        // println!("+");
+                        Some(v)
+                    } else { 
+                        // println!("-");
+                        None 
+                    }
+                }).collect::<Vec<_>>();
+            if diag.len() == 1 {
+                break;
+            }
+        }
+        diag.pop().unwrap()
+    }
+}
+
+impl Solver for BinaryDiagnostic {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
+    const NAME: &'static str = "bindiag";
+
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        BinaryDiagnostic::from_reader(input)
+    }
+
+    fn part1(&self) -> u32 {
+        self.gamma() * self.episilon()
+    }
+
+    fn part2(&self) -> u32 {
+        self.oxygen() * self.co2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_bindiag_part1() {
+        const GAMMA: u32 = 22;
+        const EPSILON: u32 = 9;
+        let report = vec![
+            "00100", "11110", "10110", "10111", "10101", "01111",
+            "00111", "11100", "10000", "11001", "00010", "01010",
+        ];
+        // let diag = BinaryDiagnostic { report_width: 5, report, };
+        let report: Vec<String> = report.iter().map(|s| s.to_string()).collect();
+        let diag = BinaryDiagnostic::from_vecstring(&report).unwrap();
+        assert_eq!(GAMMA, diag.gamma());
+        assert_eq!(EPSILON, diag.episilon());
+    }
+
+    #[test]
+    fn test_bindiag_part2() {
+        const OXYGEN: u32 = 23;
+        const CO2: u32 = 10;
+        let report = vec![
+            "00100", "11110", "10110", "10111", "10101", "01111",
+            "00111", "11100", "10000", "11001", "00010", "01010",
+        ];
+        // let diag = BinaryDiagnostic { report_width: 5, report, };
+        let report: Vec<String> = report.iter().map(|s| s.to_string()).collect();
+        let diag = BinaryDiagnostic::from_vecstring(&report).unwrap();
+        assert_eq!(OXYGEN, diag.oxygen());
+        assert_eq!(CO2, diag.co2());
+    }
+
+    #[test]
+    fn test_bindiag_bad_report() {
+        let report = ["00100", "11110", "10210"];
+        let report: Vec<String> = report.iter().map(|s| s.to_string()).collect();
+        let err = BinaryDiagnostic::from_vecstring(&report).unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert_eq!(err.column(), 3);
+    }
+}
\ No newline at end of file
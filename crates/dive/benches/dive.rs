@@ -0,0 +1,84 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use dive::SubmarineCommands;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is 1000 commands.  Aim is kept small and never
+ * goes negative, since the submarine can't fly.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let mut aim: i64 = 0;
+    (0..1000 * scale)
+        .map(|_| {
+            let distance = rng.range(1, 9);
+            match rng.below(3) {
+                0 => format!("forward {}", distance),
+                1 if aim >= distance => {
+                    aim -= distance;
+                    format!("up {}", distance)
+                },
+                _ if aim > 50 => format!("forward {}", distance),
+                _ => {
+                    aim += distance;
+                    format!("down {}", distance)
+                },
+            }
+        })
+        .collect()
+}
+
+/* Answers worked out straight from the text in i128, to check the
+ * solver gets them right at every scale before timing it.
+ */
+fn expected(input: &[String]) -> (u64, u64) {
+    let (mut horizontal, mut depth, mut aim, mut aimed_depth): (i128, i128, i128, i128) = (0, 0, 0, 0);
+    for line in input {
+        let (direction, distance) = line.split_once(' ').unwrap();
+        let distance: i128 = distance.parse().unwrap();
+        match direction {
+            "forward" => {
+                horizontal += distance;
+                aimed_depth += aim * distance;
+            },
+            "down" => {
+                depth += distance;
+                aim += distance;
+            },
+            _ => {
+                depth -= distance;
+                aim -= distance;
+            },
+        }
+    }
+    ((horizontal * depth) as u64, (horizontal * aimed_depth) as u64)
+}
+
+fn parse(input: &[String]) -> SubmarineCommands {
+    SubmarineCommands::from_strs(input).unwrap()
+}
+
+fn bench_dive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dive");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        assert_eq!((solver.part1(), solver.part2()), expected(&input), "scale {}", scale);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_dive);
+criterion_main!(benches);
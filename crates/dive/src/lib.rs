@@ -0,0 +1,264 @@
+/*
+ * https://adventofcode.com/2021/day/2
+ */
+use std::io::BufRead;
+use std::str::FromStr;
+
+use util::{ParseError, Solver};
+
+#[derive(Clone, Copy, Debug)]
+pub enum SubmarineDirection {
+    Forward,
+    Down,
+    Up
+}
+
+impl FromStr for SubmarineDirection {
+    type Err = String;
+
+    fn from_str(direction: &str) -> Result<SubmarineDirection, String> {
+        match direction {
+            "forward" => Ok(SubmarineDirection::Forward),
+            "down" => Ok(SubmarineDirection::Down),
+            "up" => Ok(SubmarineDirection::Up),
+            _ => Err("expected forward, down or up".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SubmarineCommand {
+    direction: SubmarineDirection,
+    distance: u64,
+}
+
+impl SubmarineCommand {
+    fn from_str(command: &str) -> Result<SubmarineCommand, ParseError> {
+        let command_tuple: Vec<&str> = command.split_whitespace().collect();
+        if command_tuple.len() != 2 {
+            return Err(ParseError::new("expected a command like \"forward 5\"").at_column(1));
+        }
+        let direction: SubmarineDirection = util::parse_field(command, command_tuple[0])?;
+        let distance: u64 = util::parse_field(command, command_tuple[1])?;
+        Ok(SubmarineCommand {
+            direction,
+            distance,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SubmarineCommands {
+    commands: Vec<SubmarineCommand>
+}
+
+impl SubmarineCommands {
+    pub fn from_reader<R: BufRead>(input: R) -> Result<SubmarineCommands, ParseError> {
+        let commands = util::read_lines(input)?;
+        SubmarineCommands::from_strs(&commands)
+    }
+
+    /* Up and down change the depth in part 1 and the aim in part 2
+     * by the same amounts, so one running total checks that neither
+     * ever goes above the surface.
+     */
+    pub fn from_strs(command_strs: &[String]) -> Result<SubmarineCommands, ParseError> {
+        let mut depth: u64 = 0;
+        let submarine_commands = util::parse_lines(command_strs, |line| {
+            let command = SubmarineCommand::from_str(line)?;
+            let distance = command.distance;
+            depth = match command.direction {
+                SubmarineDirection::Forward => depth,
+                SubmarineDirection::Down => depth + distance,
+                SubmarineDirection::Up => depth.checked_sub(distance).ok_or_else(|| {
+                    let field = line.split_whitespace().last().unwrap_or(line);
+                    ParseError::new(format!("up {} would take the submarine above the surface", distance))
+                        .in_field(line, field)
+                })?,
+            };
+            Ok(command)
+        })?;
+        Ok(SubmarineCommands {
+            commands: submarine_commands
+        })
+    }
+
+}
+
+impl IntoIterator for &SubmarineCommands {
+    type Item = SubmarineCommand;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commands.clone().into_iter()
+    }
+}
+
+/* Positions are u64, as depth grows with aim times distance and
+ * long courses go well past 32 bits.
+ */
+pub struct Submarine {
+    aim: u64,
+    pos_horizontal: u64,
+    pos_depth: u64,
+}
+
+
+impl Submarine {
+    pub fn new() -> Submarine {
+        Submarine {
+            aim: 0,
+            pos_horizontal: 0,
+            pos_depth: 0,
+        }
+    }
+
+    /* Part 1 has us apply commands using a misunderstanding of how the
+     * Submarine works.  I left it in so I can still print out both
+     * part's solutions.
+     */
+    pub fn apply_commands_wrong(&mut self, commands: &SubmarineCommands) {
+        for command in commands {
+            match command.direction {
+                SubmarineDirection::Forward => {
+                    self.pos_horizontal += command.distance;
+                },
+                SubmarineDirection::Up => {
+                    self.pos_depth -= command.distance;
+                },
+                SubmarineDirection::Down => {
+                    self.pos_depth += command.distance;
+                },
+            }
+        }
+    }
+
+    pub fn apply_commands(&mut self, commands: &SubmarineCommands) {
+        for command in commands {
+            match command.direction {
+                SubmarineDirection::Forward => {
+                    self.pos_horizontal += command.distance;
+                    self.pos_depth += command.distance * self.aim;
+                },
+                SubmarineDirection::Up => {
+                    self.aim -= command.distance;
+                },
+                SubmarineDirection::Down => {
+                    self.aim += command.distance;
+                },
+            }
+        }
+    }
+
+    pub fn position_horizontal(&self) -> u64 {
+        self.pos_horizontal
+    }
+
+    pub fn position_depth(&self) -> u64 {
+        self.pos_depth
+    }
+}
+
+impl Default for Submarine {
+    fn default() -> Self {
+        Submarine::new()
+    }
+}
+
+impl Solver for SubmarineCommands {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Dive!";
+    const NAME: &'static str = "dive";
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        SubmarineCommands::from_reader(input)
+    }
+
+    fn part1(&self) -> u64 {
+        let mut submarine = Submarine::new();
+        submarine.apply_commands_wrong(self);
+        submarine.position_horizontal() * submarine.position_depth()
+    }
+
+    fn part2(&self) -> u64 {
+        let mut submarine = Submarine::new();
+        submarine.apply_commands(self);
+        submarine.position_horizontal() * submarine.position_depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_dive_part1() {
+        /* The dataset and increase count were given on the webpage. */
+        const POSITION_HORIZONTAL: u64 = 15;
+        const POSITION_DEPTH: u64 = 10;
+        let commands = vec![
+            "forward 5".to_string(),
+            "down 5".to_string(),
+            "forward 8".to_string(),
+            "up 3".to_string(),
+            "down 8".to_string(),
+            "forward 2".to_string(),
+        ];
+        let mut submarine = Submarine::new();
+        let commands = SubmarineCommands::from_strs(&commands).unwrap();
+        submarine.apply_commands_wrong(&commands);
+        assert_eq!(submarine.position_horizontal(), POSITION_HORIZONTAL);
+        assert_eq!(submarine.position_depth(), POSITION_DEPTH);
+    }
+
+    #[test]
+    fn test_dive_part2() {
+        /* The dataset and increase count were given on the webpage. */
+        const POSITION_HORIZONTAL: u64 = 15;
+        const POSITION_DEPTH: u64 = 60;
+        let commands = vec![
+            "forward 5".to_string(),
+            "down 5".to_string(),
+            "forward 8".to_string(),
+            "up 3".to_string(),
+            "down 8".to_string(),
+            "forward 2".to_string(),
+        ];
+
+        let mut submarine = Submarine::new();
+        let commands = SubmarineCommands::from_strs(&commands).unwrap();
+        submarine.apply_commands(&commands);
+        assert_eq!(submarine.position_horizontal(), POSITION_HORIZONTAL);
+        assert_eq!(submarine.position_depth(), POSITION_DEPTH);
+    }
+
+    #[test]
+    fn test_dive_bad_direction() {
+        let commands = vec![
+            "forward 5".to_string(),
+            "sideways 5".to_string(),
+        ];
+        let err = SubmarineCommands::from_strs(&commands).unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert_eq!(err.column(), 1);
+    }
+
+    #[test]
+    fn test_dive_above_surface() {
+        let err = SubmarineCommands::from_strs(&["up 5".to_string()]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 4));
+        assert!(err.to_string().starts_with("error: up 5 would take the submarine above the surface"));
+
+        let commands = vec![
+            "down 3".to_string(),
+            "forward 2".to_string(),
+            "up 3".to_string(),
+            "up 1".to_string(),
+        ];
+        let err = SubmarineCommands::from_strs(&commands).unwrap_err();
+        assert_eq!(err.line(), 4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use giantsquid::BingoGame;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is a call order of 0-99 and 100 bingo cards.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let mut numbers: Vec<u64> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let calls: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+    let mut lines = vec![calls.join(",")];
+    for _ in 0..100 * scale {
+        rng.shuffle(&mut numbers);
+        lines.push(String::new());
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            lines.push(row.join(" "));
+        }
+    }
+    lines
+}
+
+fn parse(input: &[String]) -> BingoGame {
+    BingoGame::new(input).unwrap()
+}
+
+fn bench_giantsquid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("giantsquid");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_giantsquid);
+criterion_main!(benches);
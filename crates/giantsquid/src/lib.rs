@@ -0,0 +1,244 @@
+/*
+ * https://adventofcode.com/2021/day/4
+ */
+
+use std::io::BufRead;
+use std::fmt;
+
+use colored::{ColoredString, Colorize};
+
+use util::{Grid, ParseError, Solver};
+
+const CARD_SIZE: usize = 5;
+
+#[derive(Clone, Debug)]
+pub struct BingoCard {
+    card: Grid<u8>,
+    marked: Grid<bool>,
+}
+
+impl BingoCard {
+    /* Builds a card from its five rows; first_line is the line
+     * number of the first row, for error reporting.
+     */
+    fn new(card_raw: &[String], first_line: usize) -> Result<BingoCard, ParseError> {
+        let card: Grid<u8> = Grid::parse_rows(card_raw, first_line, |row| {
+            row.split_whitespace().map(|s| util::parse_field(row, s)).collect()
+        })?;
+        if card.width() != CARD_SIZE {
+            return Err(ParseError::new(format!("expected 5 numbers in card row, found {}", card.width()))
+                .on_line(first_line, &card_raw[0]));
+        }
+        Ok(BingoCard {
+            marked: Grid::new(CARD_SIZE, card.height(), false),
+            card,
+        })
+    }
+
+    /* Check the card for number called; if present,
+     * set the marked flag.
+     */
+    fn call_number(&mut self, number: u8) {
+        for (x, y) in self.card.positions() {
+            if self.card[(x, y)] == number {
+                self.marked[(x, y)] = true;
+            }
+        }
+    }
+
+    /* Returns true if this card is a winner */
+    fn is_winner(&self) -> bool {
+        let h = self.is_winner_horiz();
+        let v = self.is_winner_vert();
+        h || v
+    }
+
+    /* Check for 5-in-a-row horizontally */
+    fn is_winner_horiz(&self) -> bool {
+        self.marked.rows().any(|row| row.iter().all(|&m| m))
+    }
+
+    /* Check for 5-in-a-row vertically */
+    fn is_winner_vert(&self) -> bool {
+        self.marked.columns().any(|mut column| column.all(|&m| m))
+    }
+
+    fn score(&self) -> u32 {
+        self.card
+            .iter()
+            .zip(self.marked.iter())
+            .filter(|(_, &marked)| !marked)
+            .map(|(&v, _)| v as u32)
+            .sum()
+    }
+}
+
+impl fmt::Display for BingoCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let card = self.card.positions().map(|(x, y)| {
+            let v = self.card[(x, y)].to_string();
+            if self.marked[(x, y)] {
+                v.red().bold()
+            } else {
+                v.white()
+            }
+        });
+        let card: Grid<ColoredString> = Grid::from_vec(self.card.width(), self.card.height(), card.collect());
+        writeln!(f, "{:3}", card)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BingoGame {
+    call_order: Vec<u8>,
+    cards: Vec<BingoCard>,
+}
+
+impl BingoGame {
+    pub fn from_reader<R: BufRead>(input: R) -> Result<BingoGame, ParseError> {
+        let game_state = util::read_lines(input)?;
+        BingoGame::new(&game_state)
+    }
+
+    pub fn new(game_state: &[String]) -> Result<BingoGame, ParseError> {
+        /* First line is the number calls, and then a series of line-break and cards. */
+        let mut cards: Vec<BingoCard> = Vec::new();
+        let calls = match game_state.first() {
+            Some(calls) => calls,
+            None => return Err(ParseError::new("no number calls in input")),
+        };
+        let call_order: Vec<u8> =
+            calls
+            .split(',')
+            .map(|v| util::parse_field(calls, v))
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.on_line(1, calls))?;
+
+        /* Iterate over next chunks of newline
+         * and 5 x 5 game boards until end-of-lines
+         * Each of these 5-line chunks should
+         * be passed to BingoCard::new()
+         */
+        let mut i = 1;
+        while i < game_state.len() {
+            if !game_state[i].is_empty() {
+                return Err(ParseError::new("expected a blank line between cards")
+                    .on_line(i + 1, &game_state[i]));
+            }
+            let rows = &game_state[(i + 1)..game_state.len().min(i + 6)];
+            if rows.len() < 5 {
+                let last = game_state.len();
+                return Err(ParseError::new(format!("bingo card ends after {} of 5 rows", rows.len()))
+                    .on_line(last, &game_state[last - 1]));
+            }
+            cards.push(BingoCard::new(rows, i + 2)?);
+            i += 6;
+        }
+
+        Ok(BingoGame {
+            call_order,
+            cards,
+        })
+    }
+
+    /* Returns the score of the winner.
+     * Winner is the first card to have 5-in-a-row. 
+     * Card score is the sum of all numbers that weren't called on the card.
+     * This is multiplied by the last number called to produce the score.
+     */
+    pub fn score_winner(&self) -> u32 {
+        let mut last_called: u8 = 0;
+        let mut score: u32 = 0;
+        let mut cards = self.cards.clone();
+        'outer: for number in &self.call_order {
+            // println!("Calling number = {}", number);
+            for card in &mut cards {
+                card.call_number(*number);
+                if card.is_winner() {
+                    score = card.score();
+                    last_called = *number;
+                    break 'outer
+                }
+            }
+        }
+        score *= last_called as u32;
+        score
+    }
+    
+    /* Returns the score of the loser.
+     * Loser is the last card to have 5-in-a-row. 
+     * Card score is the sum of all numbers that weren't called on the card.
+     * This is multiplied by the last number called to produce the score.
+     */
+    pub fn score_loser(&self) -> u32 {
+        let mut cards = self.cards.clone();
+        let mut last_called: u8 = 0;
+        let mut score: u32 = 0;
+        for number in &self.call_order {
+            cards.iter_mut().for_each(|c| c.call_number(*number));
+            if cards.len() == 1 && cards[0].is_winner() {
+                last_called = *number;
+                score = cards[0].score();
+                break;
+            }
+            cards.retain(|c| !c.is_winner());
+        }
+        score *= last_called as u32;
+        score
+    }
+}
+
+impl Solver for BingoGame {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Giant Squid";
+    const NAME: &'static str = "giantsquid";
+
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        BingoGame::from_reader(input)
+    }
+
+    fn part1(&self) -> u32 {
+        self.score_winner()
+    }
+
+    fn part2(&self) -> u32 {
+        self.score_loser()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{PathBuf};
+    
+    #[test]
+    fn test_giantsquid_part1() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("giantsquid_test.txt");
+        const SCORE_WINNER: u32 = 4512;
+        let bingo_game = BingoGame::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(bingo_game.score_winner(), SCORE_WINNER);
+    }
+
+    #[test]
+    fn test_giantsquid_part2() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("giantsquid_test.txt");
+        const SCORE_LOSER: u32 = 1924;
+        let bingo_game = BingoGame::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(bingo_game.score_loser(), SCORE_LOSER);
+    }
+
+    #[test]
+    fn test_giantsquid_truncated_card() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("giantsquid_test.txt");
+        let mut game_state = util::read_lines(util::open(&input).unwrap()).unwrap();
+        game_state.truncate(game_state.len() - 2);
+        let err = BingoGame::new(&game_state).unwrap_err();
+        assert_eq!(err.line(), 17);
+    }
+}
@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use lanternfish::Lanternfish;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is a single line of 300 fish timers.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let fish: Vec<String> = (0..300 * scale)
+        .map(|_| rng.range(1, 5).to_string())
+        .collect();
+    vec![fish.join(",")]
+}
+
+fn parse(input: &[String]) -> Lanternfish {
+    Lanternfish::from_string(&input[0]).unwrap()
+}
+
+fn bench_lanternfish(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lanternfish");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_lanternfish);
+criterion_main!(benches);
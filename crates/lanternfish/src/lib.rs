@@ -0,0 +1,112 @@
+/*
+ * https://adventofcode.com/2021/day/6
+ */
+use std::io::BufRead;
+
+use util::{ParseError, Solver};
+
+// Each bin in the Vec represents how many fish in the population have a particular
+// counter.  Each round, pop the head off (shifting everyone's counter down by 1),
+// Add this value to the now counter[6] and push the new value to the end of the
+// vec (bringing us back up to 8 elements) 
+#[derive(Clone, Debug)]
+pub struct Lanternfish {
+    counter: Vec<u64>
+}
+
+impl Lanternfish {
+
+    pub fn from_reader<R: BufRead>(input: R) -> Result<Lanternfish, ParseError> {
+        let population = util::read_lines(input)?;
+        match population.first() {
+            Some(line) => Lanternfish::from_string(line).map_err(|e| e.on_line(1, line)),
+            None => Err(ParseError::new("no lanternfish in input")),
+        }
+    }
+
+    pub fn from_string(population: &str) -> Result<Lanternfish, ParseError> {
+        let mut initial_population: Vec<u32> = Vec::new();
+        for v in population.split(',') {
+            let timer: u32 = util::parse_field(population, v)?;
+            if timer > 8 {
+                return Err(ParseError::new(format!("timer must be 0 to 8, found {}", timer))
+                    .in_field(population, v));
+            }
+            initial_population.push(timer);
+        }
+        let mut lanternfish = Lanternfish { counter: Vec::new() };
+        for _ in 0..9 {
+            lanternfish.counter.push(0);
+        }
+
+        for v in initial_population {
+            lanternfish.counter[v as usize] += 1;
+        }
+        Ok(lanternfish)
+    }
+
+    pub fn population_after_day(&self, day: u32) -> u64 {
+        let mut fish = self.counter.clone();
+
+        for _ in 0..day {
+            let spawn_count = fish.remove(0);
+            fish[6] += spawn_count;
+            fish.push(spawn_count);
+        }
+        let mut population: u64 = 0;
+        for f in fish {
+            population += f;
+        }
+        population
+    }
+}
+
+impl Solver for Lanternfish {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Lanternfish";
+    const NAME: &'static str = "lanternfish";
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        Lanternfish::from_reader(input)
+    }
+
+    fn part1(&self) -> u64 {
+        self.population_after_day(80)
+    }
+
+    fn part2(&self) -> u64 {
+        self.population_after_day(256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_lanternfish_part1() {
+        const DAY: u32 = 18;
+        const EXPECTED_FISH_COUNT: u64 = 26;
+        let fish = "3,4,3,1,2".to_string();
+        let fishes = Lanternfish::from_string(&fish).unwrap();
+        assert_eq!(fishes.population_after_day(DAY), EXPECTED_FISH_COUNT);
+    }
+
+    #[test]
+    fn test_lanternfish_part2() {
+        const DAY: u32 = 256;
+        const EXPECTED_FISH_COUNT: u64 = 26984457539;
+        let fish = "3,4,3,1,2".to_string();
+        let fishes = Lanternfish::from_string(&fish).unwrap();
+        assert_eq!(fishes.population_after_day(DAY), EXPECTED_FISH_COUNT);
+    }
+
+    #[test]
+    fn test_lanternfish_bad_timer() {
+        let err = Lanternfish::from_string("3,4,9,1,2").unwrap_err();
+        assert_eq!(err.column(), 5);
+    }
+}
@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use smokebasin::HeightMap;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is a 100x100 height map.  Scaling multiplies
+ * the number of cells, so each side grows by sqrt(scale).  Roughly
+ * a third of the cells are height 9, walling off the basins.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let side = (100.0 * (scale as f64).sqrt()) as usize;
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| if rng.chance(1, 3) { '9' } else { (b'0' + rng.below(9) as u8) as char })
+                .collect()
+        })
+        .collect()
+}
+
+fn parse(input: &[String]) -> HeightMap {
+    HeightMap::from_strs(input).unwrap()
+}
+
+fn bench_smokebasin(c: &mut Criterion) {
+    let mut group = c.benchmark_group("smokebasin");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+        group.bench_with_input(BenchmarkId::new("label_basins", scale), &solver, |b, solver| {
+            b.iter(|| solver.label_basins())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_smokebasin);
+criterion_main!(benches);
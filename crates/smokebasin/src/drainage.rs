@@ -0,0 +1,166 @@
+/*
+ * Water flow over a HeightMap: each cell drains to its lowest
+ * neighbor, so basins are catchments around the sinks water ends
+ * up in, rather than regions walled off by high ground.
+ */
+
+use std::collections::VecDeque;
+
+use util::Grid;
+
+use crate::{Cell, HeightMap};
+
+/* Where water goes on a height map.  Catchments are numbered in
+ * the order of their sinks, reading the map row by row.
+ */
+#[derive(Clone, Debug)]
+pub struct Drainage {
+    downstream: Grid<Option<Cell>>,
+    catchments: Grid<u32>,
+    flow: Grid<u32>,
+    sinks: Vec<Cell>,
+    sizes: Vec<usize>,
+}
+
+impl Drainage {
+    /* The neighbor a cell drains to, or None if it's a sink. */
+    pub fn downstream(&self, cell: &Cell) -> Option<Cell> {
+        self.downstream[(cell.x, cell.y)]
+    }
+
+    /* The sink water from a cell ends up in. */
+    pub fn sink(&self, cell: &Cell) -> Cell {
+        self.sinks[self.catchment(cell) as usize]
+    }
+
+    /* Id of the catchment a cell drains into. */
+    pub fn catchment(&self, cell: &Cell) -> u32 {
+        self.catchments[(cell.x, cell.y)]
+    }
+
+    pub fn catchments(&self) -> &Grid<u32> {
+        &self.catchments
+    }
+
+    /* Number of cells whose water flows through a cell, counting
+     * the cell itself.
+     */
+    pub fn flow(&self, cell: &Cell) -> u32 {
+        self.flow[(cell.x, cell.y)]
+    }
+
+    /* Every sink, indexed by catchment id. */
+    pub fn sinks(&self) -> &[Cell] {
+        &self.sinks
+    }
+
+    /* Number of cells in each catchment, indexed by id. */
+    pub fn catchment_sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+}
+
+impl HeightMap {
+    /* The neighbor water runs to from a cell: the lowest one, if
+     * it's lower than the cell (the first in neighbor order, if
+     * several are equally low).
+     */
+    fn steepest_descent(&self, cell: &Cell) -> Option<Cell> {
+        let height = self.get_cell(cell.x, cell.y)?;
+        self.heights
+            .neighbors(cell.x, cell.y, self.connectivity)
+            .map(|(x, y)| (self.heights[(x, y)], Cell { x, y }))
+            .filter(|&(h, _)| h < height)
+            .min_by_key(|&(h, _)| h)
+            .map(|(_, lowest)| lowest)
+    }
+
+    /* Simulates water flowing downhill by steepest descent.  Every
+     * cell, walls included, drains somewhere: to the sink at the
+     * bottom of its path.  Across a flat, water runs to the nearest
+     * cell of it with a lower neighbor; a flat with none, which is a
+     * plateau as find_plateaus() finds them, is a single sink at its
+     * first cell in reading order.  Water never flows uphill, so
+     * cells are visited in order of height (and across a flat, of
+     * distance from its way out) rather than by following paths,
+     * with no recursion however long the paths get.
+     */
+    pub fn drainage(&self) -> Drainage {
+        let (width, height) = (self.heights.width(), self.heights.height());
+        let mut downstream: Grid<Option<Cell>> = Grid::from_vec(width, height,
+            self.heights.positions().map(|(x, y)| self.steepest_descent(&Cell { x, y })).collect());
+
+        /* Breadth first out from each flat's outlets, or its sink,
+         * every other cell of the flat drains to the neighbor it was
+         * reached from, a step nearer the way out.
+         */
+        let mut flats = self.flats();
+        let mut drained = vec![false; self.heights.len()];
+        for (x, y) in self.heights.positions().filter(|&(x, y)| downstream[(x, y)].is_some()) {
+            drained[flats.find(y * width + x)] = true;
+        }
+        let mut distance: Grid<Option<u32>> = Grid::new(width, height, None);
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+        for (x, y) in self.heights.positions() {
+            let flat = flats.find(y * width + x);
+            if downstream[(x, y)].is_some() || !drained[flat] {
+                drained[flat] = true;
+                distance[(x, y)] = Some(0);
+                queue.push_back((x, y));
+            }
+        }
+        while let Some((x, y)) = queue.pop_front() {
+            let steps = distance[(x, y)].map(|d| d + 1);
+            for (nx, ny) in self.heights.neighbors(x, y, self.connectivity) {
+                if self.heights[(nx, ny)] == self.heights[(x, y)] && distance[(nx, ny)].is_none() {
+                    distance[(nx, ny)] = steps;
+                    downstream[(nx, ny)] = Some(Cell { x, y });
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        let mut by_height: Vec<(usize, usize)> = self.heights.positions().collect();
+        by_height.sort_by_key(|&(x, y)| (self.heights[(x, y)], distance[(x, y)]));
+
+        let sinks: Vec<Cell> = self.heights
+            .positions()
+            .filter(|&(x, y)| downstream[(x, y)].is_none())
+            .map(|(x, y)| Cell { x, y })
+            .collect();
+        let mut catchments = Grid::new(width, height, 0);
+        for (id, sink) in sinks.iter().enumerate() {
+            catchments[(sink.x, sink.y)] = id as u32;
+        }
+        /* Going up from the bottom, each cell's downstream neighbor
+         * already knows its catchment.
+         */
+        for &(x, y) in &by_height {
+            if let Some(down) = downstream[(x, y)] {
+                catchments[(x, y)] = catchments[(down.x, down.y)];
+            }
+        }
+
+        /* Coming down from the top, each cell passes its flow on
+         * before its downstream neighbor passes it further.
+         */
+        let mut flow = Grid::new(width, height, 1);
+        for &(x, y) in by_height.iter().rev() {
+            if let Some(down) = downstream[(x, y)] {
+                flow[(down.x, down.y)] += flow[(x, y)];
+            }
+        }
+
+        let mut sizes = vec![0; sinks.len()];
+        for &id in catchments.iter() {
+            sizes[id as usize] += 1;
+        }
+        Drainage {
+            downstream,
+            catchments,
+            flow,
+            sinks,
+            sizes,
+        }
+    }
+}
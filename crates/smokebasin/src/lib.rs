@@ -0,0 +1,808 @@
+/*
+ * https://adventofcode.com/2021/day/9
+ */
+
+use std::io::{BufRead, Write};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use clap::{Arg, ArgMatches};
+
+use util::{Connectivity, Grid, ParseError, Solver, UnionFind};
+
+mod drainage;
+mod plateau;
+mod render;
+
+pub use drainage::Drainage;
+pub use plateau::LowRegion;
+pub use render::Palette;
+
+/* Height of the walls between basins, unless set otherwise. */
+const DEFAULT_WALL: i32 = 9;
+
+/* How many of the largest basins part 2 multiplies together. */
+const LARGEST_BASINS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    x: usize,
+    y: usize,
+}
+
+/* The region draining to a low point: every cell reachable from
+ * it without crossing a wall.
+ */
+#[derive(Clone, Debug)]
+pub struct Basin {
+    low_point: Cell,
+    cells: Vec<Cell>,
+}
+
+impl Basin {
+    pub fn low_point(&self) -> Cell {
+        self.low_point
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/* Summary of one basin found by HeightMap::label_basins(). */
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasinStats {
+    pub size: usize,
+    /* Lowest cell in the basin; the first one in reading order
+     * if there's a tie.
+     */
+    pub low_point: Cell,
+    pub min_height: i32,
+    pub max_height: i32,
+    pub mean_height: f64,
+    /* Top left and bottom right corners, inclusive. */
+    pub bbox: (Cell, Cell),
+}
+
+/* Every cell's basin id (None for walls), along with the stats
+ * for each basin, indexed by id.  Ids are given out in the order
+ * each basin is first reached reading the map row by row.
+ */
+#[derive(Clone, Debug)]
+pub struct BasinLabels {
+    labels: Grid<Option<u32>>,
+    basins: Vec<BasinStats>,
+}
+
+impl BasinLabels {
+    pub fn labels(&self) -> &Grid<Option<u32>> {
+        &self.labels
+    }
+
+    pub fn label(&self, cell: &Cell) -> Option<u32> {
+        self.labels.get(cell.x, cell.y).copied().flatten()
+    }
+
+    pub fn basins(&self) -> &[BasinStats] {
+        &self.basins
+    }
+}
+
+/* What part 2 counts as a basin: a region walled off by the walls
+ * (the puzzle's definition), or a catchment draining to one sink.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BasinMode {
+    #[default]
+    Walls,
+    Drainage,
+}
+
+impl FromStr for BasinMode {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<BasinMode, String> {
+        match mode {
+            "walls" => Ok(BasinMode::Walls),
+            "drainage" => Ok(BasinMode::Drainage),
+            _ => Err(format!("unknown basin mode {:?}", mode)),
+        }
+    }
+}
+
+/* What counts as a low point: a cell strictly lower than all
+ * its neighbors (the puzzle's definition), or also a flat region
+ * lower than all the cells around it.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LowPoints {
+    #[default]
+    Strict,
+    Plateaus,
+}
+
+impl FromStr for LowPoints {
+    type Err = String;
+
+    fn from_str(mode: &str) -> Result<LowPoints, String> {
+        match mode {
+            "strict" => Ok(LowPoints::Strict),
+            "plateaus" => Ok(LowPoints::Plateaus),
+            _ => Err(format!("unknown low point mode {:?}", mode)),
+        }
+    }
+}
+
+/* How heights are written in the input: a digit per cell as in the
+ * puzzle, integers separated by commas or whitespace as in an
+ * elevation raster, or whichever the input looks like.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeightFormat {
+    #[default]
+    Auto,
+    Digits,
+    Separated,
+}
+
+impl FromStr for HeightFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<HeightFormat, String> {
+        match format {
+            "auto" => Ok(HeightFormat::Auto),
+            "digits" => Ok(HeightFormat::Digits),
+            "separated" => Ok(HeightFormat::Separated),
+            _ => Err(format!("unknown height format {:?}", format)),
+        }
+    }
+}
+
+impl HeightFormat {
+    /* Separated if any row has a separator between two of its
+     * heights; whitespace around a row doesn't count.
+     */
+    fn detect(lines: &[String]) -> HeightFormat {
+        let separated = lines
+            .iter()
+            .any(|line| line.trim().contains(|c: char| c == ',' || c.is_whitespace()));
+        if separated { HeightFormat::Separated } else { HeightFormat::Digits }
+    }
+}
+
+#[derive(Debug)]
+pub struct HeightMap {
+    heights: Grid<i32>,
+    /* Cells this high or higher are walls between basins. */
+    wall: i32,
+    /* Which cells count as neighbors, for low points and basins. */
+    connectivity: Connectivity,
+    basin_mode: BasinMode,
+    low_points: LowPoints,
+}
+
+impl HeightMap {
+
+    pub fn from_reader<R: BufRead>(input: R) -> Result<HeightMap, ParseError> {
+        let ventline_strings = util::read_lines(input)?;
+        HeightMap::from_strs(&ventline_strings)
+    }
+
+    /* Heights are either one digit per cell, as in the puzzle, or
+     * integers of any size separated by commas or whitespace, as in
+     * an elevation raster.  A separator between two heights in any
+     * row means the latter.  Every row must be the same width.
+     */
+    pub fn from_strs(ventline_strings: &[String]) -> Result<HeightMap, ParseError> {
+        HeightMap::from_strs_with(ventline_strings, HeightFormat::Auto, None)
+    }
+
+    /* from_strs(), but rows shorter than the longest are padded out
+     * on the right with walls of the given height.
+     */
+    pub fn from_strs_padded(ventline_strings: &[String], wall: i32) -> Result<HeightMap, ParseError> {
+        HeightMap::from_strs_with(ventline_strings, HeightFormat::Auto, Some(wall))
+    }
+
+    /* Heights in the given format, with short rows padded out with
+     * walls of the given height (which become the map's walls) if
+     * there is one.
+     */
+    pub fn from_strs_with(ventline_strings: &[String], format: HeightFormat, pad: Option<i32>) -> Result<HeightMap, ParseError> {
+        let heightmap = HeightMap::parse_strs(ventline_strings, format, pad)?;
+        Ok(match pad {
+            Some(wall) => heightmap.with_wall(wall),
+            None => heightmap,
+        })
+    }
+
+    fn parse_strs(ventline_strings: &[String], format: HeightFormat, pad: Option<i32>) -> Result<HeightMap, ParseError> {
+        /* Blank lines after the map aren't rows of it. */
+        let rows = ventline_strings
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |last| last + 1);
+        let lines = &ventline_strings[..rows];
+        if lines.is_empty() {
+            return Err(ParseError::new("no height map in input"));
+        }
+        let format = match format {
+            HeightFormat::Auto => HeightFormat::detect(lines),
+            format => format,
+        };
+        let heights = if format == HeightFormat::Separated {
+            let parse_row = |line: &str| {
+                line.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|field| !field.is_empty())
+                    .map(|field| util::parse_field(line, field))
+                    .collect()
+            };
+            match pad {
+                Some(wall) => Grid::parse_rows_padded(lines, 1, wall, parse_row)?,
+                None => Grid::parse_rows(lines, 1, parse_row)?,
+            }
+        } else {
+            let parse_cell = |c: char| match c.to_digit(10) {
+                Some(v) => Ok(v as i32),
+                None => Err(format!("expected a height 0-9, found {:?}", c)),
+            };
+            match pad {
+                Some(wall) => Grid::parse_padded(lines, wall, parse_cell)?,
+                None => Grid::parse(lines, parse_cell)?,
+            }
+        };
+        Ok(HeightMap {
+            heights,
+            wall: DEFAULT_WALL,
+            connectivity: Connectivity::Four,
+            basin_mode: BasinMode::Walls,
+            low_points: LowPoints::Strict,
+        })
+    }
+
+    /* Use diagonal neighbors too (Connectivity::Eight) when finding
+     * low points and filling basins.
+     */
+    pub fn with_connectivity(mut self, connectivity: Connectivity) -> HeightMap {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /* Make cells of this height and above the walls between basins,
+     * instead of 9s.
+     */
+    pub fn with_wall(mut self, wall: i32) -> HeightMap {
+        self.wall = wall;
+        self
+    }
+
+    fn is_wall(&self, height: i32) -> bool {
+        height >= self.wall
+    }
+
+    pub fn with_basin_mode(mut self, basin_mode: BasinMode) -> HeightMap {
+        self.basin_mode = basin_mode;
+        self
+    }
+
+    pub fn with_low_points(mut self, low_points: LowPoints) -> HeightMap {
+        self.low_points = low_points;
+        self
+    }
+
+    pub fn risk_level(&self) -> i64 {
+        self.low_regions().iter().map(LowRegion::risk).sum()
+    }
+
+    /* Low points as regions, for either low point mode; in strict
+     * mode each is a single cell.
+     */
+    pub fn low_regions(&self) -> Vec<LowRegion> {
+        match self.low_points {
+            LowPoints::Strict => self.get_lowest_points()
+                .into_iter()
+                .map(|cell| LowRegion::single(cell, self.heights[(cell.x, cell.y)]))
+                .collect(),
+            LowPoints::Plateaus => self.find_plateaus(),
+        }
+    }
+
+    /* Iterate over the grid, checking each cell to
+     * see if it's less than all its neighbors.
+     */
+    pub fn get_lowest_points(&self) -> Vec<Cell> {
+        self.heights
+            .positions()
+            .filter(|&(x, y)| self.check_low_point(x, y))
+            .map(|(x, y)| Cell { x, y })
+            .collect()
+    }
+
+    pub fn check_low_point(&self, x: usize, y: usize) -> bool {
+        let cell = self.heights[(x, y)];
+        self.heights
+            .neighbors(x, y, self.connectivity)
+            .all(|neighbor| cell < self.heights[neighbor])
+    }
+
+    pub fn get_cell(&self, x: usize, y: usize) -> Option<i32> {
+        self.heights.get(x, y).copied()
+    }
+
+    /* Flood fill out from a cell (normally a low point), stopping
+     * at walls.  Uses an explicit stack rather than
+     * recursion so big basins can't overflow the call stack.
+     */
+    pub fn find_basin(&self, cell: &Cell) -> Basin {
+        let mut seen = Grid::new(self.heights.width(), self.heights.height(), false);
+        self.fill_basin(cell, &mut seen)
+    }
+
+    /* Flood fill, skipping cells already seen.  Basins never
+     * overlap, so one seen grid can be shared by all of them.
+     */
+    fn fill_basin(&self, cell: &Cell, seen: &mut Grid<bool>) -> Basin {
+        let mut stack = vec![(cell.x, cell.y)];
+        let mut cells: Vec<Cell> = Vec::new();
+        seen[(cell.x, cell.y)] = true;
+        while let Some((x, y)) = stack.pop() {
+            if self.is_wall(self.heights[(x, y)]) {
+                continue;
+            }
+            cells.push(Cell { x, y });
+            for neighbor in self.heights.neighbors(x, y, self.connectivity) {
+                if !seen[neighbor] {
+                    seen[neighbor] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        Basin {
+            low_point: *cell,
+            cells,
+        }
+    }
+
+    /* The basin around each low point (or low region).  A low point
+     * that's already in an earlier one's basin (which can happen with
+     * diagonal neighbors), or that's as high as the walls, doesn't get
+     * a basin of its own.
+     */
+    pub fn find_basins(&self) -> Vec<Basin> {
+        let mut seen = Grid::new(self.heights.width(), self.heights.height(), false);
+        self.low_regions()
+            .iter()
+            .filter(|region| !self.is_wall(region.height()))
+            .map(LowRegion::first_cell)
+            .filter_map(|cell| {
+                if seen[(cell.x, cell.y)] {
+                    None
+                } else {
+                    Some(self.fill_basin(&cell, &mut seen))
+                }
+            })
+            .collect()
+    }
+
+    /* Labels every cell outside the walls with the basin it belongs
+     * to, without flood filling: one pass over the map joins each
+     * cell to the basin of its neighbors already passed (above and
+     * left of it) in a union-find, and a second pass numbers the
+     * basins and gathers their stats.  Scales to multi-megapixel maps
+     * with no recursion and no per-basin allocation.
+     */
+    pub fn label_basins(&self) -> BasinLabels {
+        let heights = &self.heights;
+        let width = heights.width();
+        let mut sets = UnionFind::new(heights.len());
+        for (x, y) in heights.positions() {
+            if self.is_wall(heights[(x, y)]) {
+                continue;
+            }
+            for (nx, ny) in heights.neighbors(x, y, self.connectivity) {
+                if (ny, nx) < (y, x) && !self.is_wall(heights[(nx, ny)]) {
+                    sets.union(y * width + x, ny * width + nx);
+                }
+            }
+        }
+
+        let mut ids: Vec<Option<u32>> = vec![None; heights.len()];
+        let mut basins: Vec<BasinStats> = Vec::new();
+        let mut totals: Vec<i64> = Vec::new();
+        let labels = heights.positions().map(|(x, y)| {
+            let height = heights[(x, y)];
+            if self.is_wall(height) {
+                return None;
+            }
+            let root = sets.find(y * width + x);
+            let cell = Cell { x, y };
+            let id = *ids[root].get_or_insert_with(|| {
+                basins.push(BasinStats {
+                    size: 0,
+                    low_point: cell,
+                    min_height: height,
+                    max_height: height,
+                    mean_height: 0.0,
+                    bbox: (cell, cell),
+                });
+                totals.push(0);
+                (basins.len() - 1) as u32
+            });
+            let basin = &mut basins[id as usize];
+            basin.size += 1;
+            totals[id as usize] += height as i64;
+            if height < basin.min_height {
+                basin.min_height = height;
+                basin.low_point = cell;
+            }
+            basin.max_height = basin.max_height.max(height);
+            basin.bbox.0.x = basin.bbox.0.x.min(x);
+            basin.bbox.1.x = basin.bbox.1.x.max(x);
+            basin.bbox.1.y = y;
+            Some(id)
+        });
+        let labels = Grid::from_vec(width, heights.height(), labels.collect());
+        for (basin, total) in basins.iter_mut().zip(totals) {
+            basin.mean_height = total as f64 / basin.size as f64;
+        }
+        BasinLabels { labels, basins }
+    }
+
+    /* Product of the sizes of the three largest basins. */
+    pub fn largest_basins_product(&self) -> usize {
+        let mut sizes: Vec<usize> = match self.basin_mode {
+            BasinMode::Walls => self.find_basins().iter().map(Basin::size).collect(),
+            BasinMode::Drainage => self.drainage().catchment_sizes().to_vec(),
+        };
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.iter().take(LARGEST_BASINS).product()
+    }
+}
+
+/* Single digits are written the way the puzzle has them; anything
+ * else is written in space separated columns, so it reads back in.
+ */
+impl fmt::Display for HeightMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.heights.iter().all(|height| (0..=9).contains(height)) {
+            write!(f, "{}", self.heights)?;
+        } else {
+            let width = self.heights.iter().map(|height| height.to_string().len()).max().unwrap_or(0);
+            write!(f, "{:1$}", self.heights, width + 1)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Solver for HeightMap {
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Smoke Basin";
+    const NAME: &'static str = "smokebasin";
+
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        HeightMap::from_reader(input)
+    }
+
+    fn args<'help>() -> Vec<Arg<'help>> {
+        vec![
+            Arg::new("connectivity")
+                .about("Neighbors of a cell for low points and basins: 4 (edges) or 8 (with diagonals)")
+                .long("connectivity")
+                .takes_value(true)
+                .possible_values(["4", "8"])
+                .default_value("4"),
+            Arg::new("wall")
+                .about("Height at and above which cells are walls between basins")
+                .long("wall")
+                .value_name("HEIGHT")
+                .takes_value(true)
+                .validator(|height| height.parse::<i32>())
+                .default_value("9"),
+            Arg::new("heights")
+                .about("How heights are written: a digit per cell, integers separated by commas or whitespace, or whichever the input looks like")
+                .long("heights")
+                .takes_value(true)
+                .possible_values(["auto", "digits", "separated"])
+                .default_value("auto"),
+            Arg::new("pad-ragged")
+                .about("Pad rows shorter than the longest out with walls, instead of rejecting the input")
+                .long("pad-ragged"),
+            Arg::new("basins")
+                .about("What part 2 counts as a basin: regions walled off by walls, or catchments draining to one sink")
+                .long("basins")
+                .takes_value(true)
+                .possible_values(["walls", "drainage"])
+                .default_value("walls"),
+            Arg::new("low-points")
+                .about("Low points are single cells lower than all their neighbors, or also flat regions lower than all around them")
+                .long("low-points")
+                .takes_value(true)
+                .possible_values(["strict", "plateaus"])
+                .default_value("strict"),
+            Arg::new("render")
+                .about("Write the height map to a .png or .ppm image, low points highlighted")
+                .long("render")
+                .value_name("IMAGE")
+                .takes_value(true),
+            Arg::new("palette")
+                .about("Colors for --render: gray, color, or a color per basin")
+                .long("palette")
+                .takes_value(true)
+                .possible_values(["gray", "color", "basins"])
+                .default_value("color"),
+            Arg::new("render-scale")
+                .about("Draw each cell as an N x N square in --render")
+                .long("render-scale")
+                .value_name("N")
+                .takes_value(true)
+                .validator(|n| n.parse::<usize>())
+                .default_value("1"),
+        ]
+    }
+
+    fn parse_with<R: BufRead>(input: R, argmatches: &ArgMatches) -> Result<Self, ParseError> {
+        let format = argmatches.value_of("heights").map_or(HeightFormat::Auto, |format| format.parse().unwrap_or_default());
+        let pad = if argmatches.is_present("pad-ragged") {
+            Some(argmatches.value_of("wall").map_or(DEFAULT_WALL, |wall| wall.parse().unwrap_or(DEFAULT_WALL)))
+        } else {
+            None
+        };
+        let mut heightmap = HeightMap::from_strs_with(&util::read_lines(input)?, format, pad)?;
+        heightmap.configure(argmatches);
+        Ok(heightmap)
+    }
+
+    fn configure(&mut self, argmatches: &ArgMatches) {
+        if let Some(connectivity) = argmatches.value_of("connectivity") {
+            self.connectivity = connectivity.parse().unwrap_or_default();
+        }
+        if let Some(wall) = argmatches.value_of("wall") {
+            self.wall = wall.parse().unwrap_or(DEFAULT_WALL);
+        }
+        if let Some(basin_mode) = argmatches.value_of("basins") {
+            self.basin_mode = basin_mode.parse().unwrap_or_default();
+        }
+        if let Some(low_points) = argmatches.value_of("low-points") {
+            self.low_points = low_points.parse().unwrap_or_default();
+        }
+    }
+
+    fn wants_export(argmatches: &ArgMatches) -> bool {
+        argmatches.is_present("render")
+    }
+
+    fn export(&self, argmatches: &ArgMatches, _out: &mut dyn Write) -> Result<(), String> {
+        let path = match argmatches.value_of("render") {
+            Some(path) => Path::new(path),
+            None => return Ok(()),
+        };
+        let palette = argmatches.value_of("palette").map_or(Ok(Palette::default()), str::parse)?;
+        let scale = argmatches.value_of("render-scale").map_or(1, |n| n.parse().unwrap_or(1));
+        let image = util::image::upscale(&self.render(palette), scale);
+        util::image::save_image(&image, path)
+            .map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+    }
+
+    fn part1(&self) -> i64 {
+        self.risk_level()
+    }
+
+    fn part2(&self) -> usize {
+        self.largest_basins_product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{PathBuf};
+    
+    #[test]
+    fn test_smokebasin_part1() {
+        const RISK_LEVEL: i64 = 15;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(heightmap.risk_level(), RISK_LEVEL);
+    }
+
+    #[test]
+    fn test_smokebasin_part2() {
+        const BASIN_SIZES: [usize; 4] = [3, 9, 14, 9];
+        const LARGEST_BASINS_PRODUCT: usize = 1134;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        let sizes: Vec<usize> = heightmap.find_basins().iter().map(Basin::size).collect();
+        assert_eq!(sizes, BASIN_SIZES);
+        assert_eq!(heightmap.largest_basins_product(), LARGEST_BASINS_PRODUCT);
+    }
+
+    #[test]
+    fn test_smokebasin_label_basins() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        let labels = heightmap.label_basins();
+        for (id, basin) in heightmap.find_basins().iter().enumerate() {
+            assert!(basin.cells().iter().all(|cell| labels.label(cell) == Some(id as u32)));
+            assert_eq!(labels.basins()[id].size, basin.size());
+            assert_eq!(labels.basins()[id].low_point, basin.low_point());
+        }
+        assert_eq!(labels.label(&Cell { x: 2, y: 0 }), None);
+        assert_eq!(labels.basins()[2], BasinStats {
+            size: 14,
+            low_point: Cell { x: 2, y: 2 },
+            min_height: 5,
+            max_height: 8,
+            mean_height: 101.0 / 14.0,
+            bbox: (Cell { x: 0, y: 1 }, Cell { x: 5, y: 4 }),
+        });
+    }
+
+    #[test]
+    fn test_smokebasin_connectivity() {
+        let heights: Vec<String> = ["9999", "9199", "9929", "9999"].iter().map(|s| s.to_string()).collect();
+        let heightmap = HeightMap::from_strs(&heights).unwrap();
+        assert_eq!(heightmap.risk_level(), 5);
+        assert_eq!(heightmap.find_basins().len(), 2);
+        assert_eq!(heightmap.label_basins().basins().len(), 2);
+
+        let heightmap = heightmap.with_connectivity(Connectivity::Eight);
+        assert_eq!(heightmap.risk_level(), 2);
+        assert_eq!(heightmap.find_basins().len(), 1);
+        assert_eq!(heightmap.label_basins().basins()[0].size, 2);
+    }
+
+    #[test]
+    fn test_smokebasin_drainage() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        let drainage = heightmap.drainage();
+        for low_point in heightmap.get_lowest_points() {
+            assert_eq!(drainage.downstream(&low_point), None);
+            let id = drainage.catchment(&low_point) as usize;
+            assert_eq!(drainage.flow(&low_point) as usize, drainage.catchment_sizes()[id]);
+        }
+        let corner = Cell { x: 0, y: 0 };
+        assert_eq!(drainage.downstream(&corner), Some(Cell { x: 1, y: 0 }));
+        assert_eq!(drainage.sink(&corner), Cell { x: 1, y: 0 });
+        assert_eq!(drainage.catchment_sizes().iter().sum::<usize>(), 50);
+
+        let heightmap = heightmap.with_basin_mode(BasinMode::Drainage);
+        assert_eq!(heightmap.largest_basins_product(), 2970);
+        assert_eq!(heightmap.drainage().sinks().len(), heightmap.find_plateaus().len());
+
+        let heights: Vec<String> = ["5555", "5115", "5555"].iter().map(|s| s.to_string()).collect();
+        let drainage = HeightMap::from_strs(&heights).unwrap().drainage();
+        assert_eq!(drainage.sinks(), [Cell { x: 1, y: 1 }]);
+        assert_eq!(drainage.downstream(&Cell { x: 2, y: 1 }), Some(Cell { x: 1, y: 1 }));
+        assert_eq!(drainage.catchment_sizes(), [12]);
+        assert_eq!(drainage.flow(&Cell { x: 1, y: 1 }), 12);
+    }
+
+    #[test]
+    fn test_smokebasin_render() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        let image = heightmap.render(Palette::Gray);
+        assert_eq!((image.width(), image.height()), (10, 5));
+        assert_eq!(image[(1, 0)], [255, 0, 0]);
+        assert_eq!(image[(2, 0)], [255, 255, 255]);
+        assert_eq!(image[(0, 0)], [57, 57, 57]);
+
+        let image = heightmap.render(Palette::Basins);
+        assert_eq!(image[(2, 0)], [0, 0, 0]);
+        assert_eq!(image[(1, 0)], [255, 255, 255]);
+        assert_ne!(image[(0, 0)], image[(9, 0)]);
+    }
+
+    #[test]
+    fn test_smokebasin_plateaus() {
+        let heights: Vec<String> = ["9999", "9119", "9929", "9999"].iter().map(|s| s.to_string()).collect();
+        let heightmap = HeightMap::from_strs(&heights).unwrap();
+        assert_eq!(heightmap.risk_level(), 0);
+
+        let heightmap = heightmap.with_low_points(LowPoints::Plateaus);
+        let regions = heightmap.low_regions();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].cells(), [Cell { x: 1, y: 1 }, Cell { x: 2, y: 1 }]);
+        assert_eq!(heightmap.risk_level(), 2);
+        assert_eq!(heightmap.largest_basins_product(), 3);
+
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("smokebasin_test.txt");
+        let heightmap = HeightMap::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(heightmap.with_low_points(LowPoints::Plateaus).risk_level(), 15);
+    }
+
+    #[test]
+    fn test_smokebasin_bad_height() {
+        let heights = vec!["2199".to_string(), "39x7".to_string()];
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert_eq!(err.column(), 3);
+    }
+
+    #[test]
+    fn test_smokebasin_separated_heights() {
+        let heights = vec!["10,20,1000,40".to_string(), "15, 25, 1000, 35".to_string()];
+        let heightmap = HeightMap::from_strs(&heights).unwrap().with_wall(1000);
+        assert_eq!(heightmap.get_cell(2, 1), Some(1000));
+        assert_eq!(heightmap.risk_level(), 47);
+        assert_eq!(heightmap.largest_basins_product(), 8);
+        assert_eq!(heightmap.to_string(), "   10   20 1000   40\n   15   25 1000   35\n\n");
+
+        let heightmap = heightmap.with_wall(30);
+        let sizes: Vec<usize> = heightmap.find_basins().iter().map(Basin::size).collect();
+        assert_eq!(sizes, [4]);
+
+        let heights = vec!["-3 0".to_string(), "7 x".to_string()];
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+
+        let heights = vec!["10,20,30".to_string(), "15, 25, 35, 45".to_string()];
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 13));
+        assert!(err.to_string().starts_with("error: expected 3 cells in row, found 4"));
+    }
+
+    #[test]
+    fn test_smokebasin_trailing_whitespace() {
+        let heights = vec!["21999 ".to_string(), "39878".to_string()];
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 6));
+        let heights = vec!["2199943210 ".to_string(), "3987894921".to_string()];
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 11));
+
+        let heights = vec![" 10,20 ".to_string(), "15,25".to_string()];
+        let heightmap = HeightMap::from_strs(&heights).unwrap();
+        assert_eq!(heightmap.get_cell(1, 0), Some(20));
+
+        let heights = vec!["21".to_string(), "39".to_string()];
+        let heightmap = HeightMap::from_strs_with(&heights, HeightFormat::Separated, None).unwrap();
+        assert_eq!(heightmap.get_cell(0, 1), Some(39));
+        let heights = vec!["2 1".to_string(), "3 9".to_string()];
+        assert!(HeightMap::from_strs_with(&heights, HeightFormat::Digits, None).is_err());
+    }
+
+    #[test]
+    fn test_smokebasin_ragged() {
+        let heights: Vec<String> = ["2199", "398", "9856", ""].iter().map(|s| s.to_string()).collect();
+        let err = HeightMap::from_strs(&heights).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert!(err.to_string().starts_with("error: expected 4 cells in row, found 3"));
+
+        let heightmap = HeightMap::from_strs_padded(&heights, 9).unwrap();
+        assert_eq!(heightmap.to_string(), "2199\n3989\n9856\n\n");
+        assert_eq!(heightmap.risk_level(), 8);
+
+        let blank = vec![String::new(), " ".to_string()];
+        assert!(HeightMap::from_strs(&blank).unwrap_err().to_string().contains("no height map in input"));
+    }
+
+    // #[test]
+    // fn test_vents_part2() {
+    //     const OVERLAPPING_VENT_THRESHOLD: i32 = 2;
+    //     const OVERLAPPING_VENT_COUNT: i32 = 12;
+    //     let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    //     input.push("vents_test.txt");
+    //     let vents_map = VentsMap::from_file(&input, true);
+    //     println!("{}", vents_map);
+    //     assert_eq!(vents_map.overlapping_vent_count(OVERLAPPING_VENT_THRESHOLD), OVERLAPPING_VENT_COUNT);
+    // }
+
+    
+  
+}
\ No newline at end of file
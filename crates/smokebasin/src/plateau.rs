@@ -0,0 +1,104 @@
+/*
+ * Low points that are flat: connected regions of equal height
+ * with every neighbor around them higher.
+ */
+
+use util::UnionFind;
+
+use crate::{Cell, HeightMap};
+
+/* A low point, or a flat region of cells at the same height that
+ * counts as one low point.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LowRegion {
+    height: i32,
+    /* In reading order, row by row. */
+    cells: Vec<Cell>,
+}
+
+impl LowRegion {
+    pub(crate) fn single(cell: Cell, height: i32) -> LowRegion {
+        LowRegion {
+            height,
+            cells: vec![cell],
+        }
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /* The region's first cell, in reading order. */
+    pub fn first_cell(&self) -> Cell {
+        self.cells[0]
+    }
+
+    /* A region counts once towards the risk level, however many
+     * cells it has, as it's a single low point.
+     */
+    pub fn risk(&self) -> i64 {
+        self.height as i64 + 1
+    }
+}
+
+impl HeightMap {
+    /* Finds every minimal plateau: a connected region of equal
+     * height where all the neighbors around its edge are higher.
+     * Strict low points are the plateaus of a single cell.  Regions
+     * are joined with a union-find, so this is one pass over the
+     * map plus one over the regions.
+     */
+    pub fn find_plateaus(&self) -> Vec<LowRegion> {
+        let heights = &self.heights;
+        let width = heights.width();
+        let index = |x: usize, y: usize| y * width + x;
+        let mut sets = self.flats();
+
+        /* Equal neighbors are all in the region, so a region is low
+         * unless one of its cells has a lower neighbor.
+         */
+        let mut low = vec![true; heights.len()];
+        for (x, y) in heights.positions() {
+            let height = heights[(x, y)];
+            if heights.neighbors(x, y, self.connectivity).any(|n| heights[n] < height) {
+                let root = sets.find(index(x, y));
+                low[root] = false;
+            }
+        }
+
+        let mut region_of: Vec<Option<usize>> = vec![None; heights.len()];
+        let mut regions: Vec<LowRegion> = Vec::new();
+        for (x, y) in heights.positions() {
+            let root = sets.find(index(x, y));
+            if !low[root] {
+                continue;
+            }
+            let region = *region_of[root].get_or_insert_with(|| {
+                regions.push(LowRegion { height: heights[(x, y)], cells: Vec::new() });
+                regions.len() - 1
+            });
+            regions[region].cells.push(Cell { x, y });
+        }
+        regions
+    }
+
+    /* Sets of cells joined to equal neighbors, by y * width + x. */
+    pub(crate) fn flats(&self) -> UnionFind {
+        let heights = &self.heights;
+        let width = heights.width();
+        let mut sets = UnionFind::new(heights.len());
+        for (x, y) in heights.positions() {
+            for (nx, ny) in heights.neighbors(x, y, self.connectivity) {
+                if heights[(nx, ny)] == heights[(x, y)] {
+                    sets.union(y * width + x, ny * width + nx);
+                }
+            }
+        }
+        sets
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Renders a HeightMap as an image, one pixel per cell.
+ */
+
+use std::str::FromStr;
+
+use util::image::{self, Rgb};
+use util::Grid;
+
+use crate::{BasinMode, HeightMap};
+
+/* Low points stand out in red, or white over colored basins. */
+const LOW_POINT: Rgb = [255, 0, 0];
+const BASIN_LOW_POINT: Rgb = [255, 255, 255];
+
+/* How cells are colored. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    /* Black (lowest) to white (highest). */
+    Gray,
+    /* Purple (lowest) through green to yellow (highest). */
+    #[default]
+    Color,
+    /* A color per basin, darker lower down, with walls in black. */
+    Basins,
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(palette: &str) -> Result<Palette, String> {
+        match palette {
+            "gray" => Ok(Palette::Gray),
+            "color" => Ok(Palette::Color),
+            "basins" => Ok(Palette::Basins),
+            _ => Err(format!("unknown palette {:?}", palette)),
+        }
+    }
+}
+
+impl HeightMap {
+    /* Image of the height map with the low points highlighted.  The
+     * basins palette uses the current basin mode: walled regions, or
+     * drainage catchments.
+     */
+    pub fn render(&self, palette: Palette) -> Grid<Rgb> {
+        let lowest = self.heights.iter().min().copied().unwrap_or(0);
+        let highest = self.heights.iter().max().copied().unwrap_or(0);
+        let range = (highest as f64 - lowest as f64).max(1.0);
+        let level = |height: i32| (height as f64 - lowest as f64) / range;
+
+        let mut image = match palette {
+            Palette::Gray => self.heights.map(|&h| image::gray((level(h) * 255.0).round() as u8)),
+            Palette::Color => self.heights.map(|&h| image::colormap(level(h))),
+            Palette::Basins => {
+                let basins: Grid<Option<u32>> = match self.basin_mode {
+                    BasinMode::Walls => self.label_basins().labels().clone(),
+                    BasinMode::Drainage => self.drainage().catchments().map(|&id| Some(id)),
+                };
+                let cells = self.heights.positions().map(|(x, y)| {
+                    let height = self.heights[(x, y)];
+                    match basins[(x, y)] {
+                        Some(id) => image::shade(image::distinct_color(id as usize), 0.5 + level(height) / 2.0),
+                        None => [0, 0, 0],
+                    }
+                });
+                Grid::from_vec(self.heights.width(), self.heights.height(), cells.collect())
+            },
+        };
+
+        let highlight = if palette == Palette::Basins { BASIN_LOW_POINT } else { LOW_POINT };
+        for region in self.low_regions() {
+            for cell in region.cells() {
+                image[(cell.x, cell.y)] = highlight;
+            }
+        }
+        image
+    }
+}
@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use sonarsweep::SonarSweep;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is 2000 depth readings, drifting deeper.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let mut depth: i64 = 150;
+    (0..2000 * scale)
+        .map(|_| {
+            depth = (depth + rng.range(-10, 20)).max(0);
+            depth.to_string()
+        })
+        .collect()
+}
+
+fn parse(input: &[String]) -> SonarSweep {
+    SonarSweep::from_strs(input).unwrap()
+}
+
+fn bench_sonarsweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sonarsweep");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sonarsweep);
+criterion_main!(benches);
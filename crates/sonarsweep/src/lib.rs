@@ -0,0 +1,121 @@
+/*
+ * https://adventofcode.com/2021/day/1
+ */
+use std::io::BufRead;
+
+use util::{ParseError, Solver};
+
+#[derive(Clone, Debug)]
+pub struct SonarSweep {
+    depths: Vec<u32>
+}
+
+impl SonarSweep {
+    pub fn from_reader<R: BufRead>(input: R) -> Result<SonarSweep, ParseError> {
+        let lines = util::read_lines(input)?;
+        SonarSweep::from_strs(&lines)
+    }
+
+    pub fn from_strs(lines: &[String]) -> Result<SonarSweep, ParseError> {
+        let depths = util::parse_lines(lines, |line| util::parse_field(line, line))?;
+        if lines.is_empty() {
+            return Err(ParseError::new("no depth measurements in input"));
+        }
+        Ok(SonarSweep {
+            depths,
+        })
+    }
+
+    /* Counts the number of times a depth measurement increases
+     * from the previous measurement.
+     */
+    pub fn increase_count(&self) -> u32 {
+        self.increase_count_sliding_window(1)
+    }
+
+    
+    pub fn increase_count_sliding_window(&self, window_size: usize) -> u32 {
+        let mut increase_count = 0;
+        let mut chunks: Vec<u32> = Vec::new();
+
+        for i in 0..self.depths.len().saturating_sub(window_size - 1) {
+            let mut sum = 0;
+            for j in 0..window_size {
+                sum += self.depths[i+j];
+            }
+            chunks.push(sum);
+        }
+
+        let mut last: Option<u32> = None;
+        for chunk in chunks {
+            if let Some(last) = last {
+                if chunk > last {
+                    increase_count += 1;
+                }    
+            }
+            last = Some(chunk);
+        }
+        increase_count
+    }
+}
+
+impl Solver for SonarSweep {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+    const NAME: &'static str = "sonarsweep";
+
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        SonarSweep::from_reader(input)
+    }
+
+    fn part1(&self) -> u32 {
+        self.increase_count()
+    }
+
+    fn part2(&self) -> u32 {
+        self.increase_count_sliding_window(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sonarsweep_count_depth_increase() {
+        /* The dataset and increase count were given on the webpage. */
+        const INCREASE_COUNT: u32 = 7;
+        let depths = vec![
+            199, 200, 208, 210, 200,
+            207, 240, 269, 260, 263
+        ];
+
+        let sweep = SonarSweep { depths, };
+        assert_eq!(sweep.increase_count(), INCREASE_COUNT);
+    }
+
+    #[test]
+    fn test_sonarsweep_bad_depth() {
+        let depths = vec!["199".to_string(), "2OO".to_string()];
+        let err = SonarSweep::from_strs(&depths).unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert_eq!(err.column(), 1);
+    }
+
+    #[test]
+    fn test_sonarsweep_count_depth_increase_sliding_window() {
+        /* This one tests the result with the sliding window. */
+        const INCREASE_COUNT: u32 = 5;
+        const WINDOW_SIZE: usize = 3;
+        let depths = vec![
+            199, 200, 208, 210, 200,
+            207, 240, 269, 260, 263
+        ];
+
+        let sweep = SonarSweep { depths, };
+        assert_eq!(sweep.increase_count_sliding_window(WINDOW_SIZE), INCREASE_COUNT);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::{ParseError, Solution, Solved, Timings};
+
+/* Summary of the wall time for one phase over every run. */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhaseStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl PhaseStats {
+    fn from_samples(mut samples: Vec<Duration>) -> PhaseStats {
+        if samples.is_empty() {
+            return PhaseStats::default();
+        }
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        PhaseStats {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / n as u32,
+            max: samples[n - 1],
+        }
+    }
+}
+
+/* Timing statistics for parsing and each part, along with the
+ * solution from the last run.
+ */
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub runs: usize,
+    pub parse: PhaseStats,
+    pub part1: PhaseStats,
+    pub part2: PhaseStats,
+    pub solution: Solution,
+}
+
+impl BenchReport {
+    pub fn from_timings(timings: &[Timings], solution: Solution) -> BenchReport {
+        let phase = |f: fn(&Timings) -> Duration| {
+            PhaseStats::from_samples(timings.iter().map(f).collect())
+        };
+        BenchReport {
+            runs: timings.len(),
+            parse: phase(|t| t.parse),
+            part1: phase(|t| t.part1),
+            part2: phase(|t| t.part2),
+            solution,
+        }
+    }
+}
+
+/* Solves the puzzle runs times (at least once), timing each phase.
+ * The last run is handed back too, for exporting from.
+ */
+pub fn bench<F>(solve: F, input: &[u8], runs: usize) -> Result<(BenchReport, Solved), ParseError>
+where
+    F: Fn(&[u8]) -> Result<Solved, ParseError>,
+{
+    let mut timings: Vec<Timings> = Vec::with_capacity(runs);
+    let mut solved = solve(input)?;
+    timings.push(solved.solution.timings);
+    for _ in 1..runs {
+        solved = solve(input)?;
+        timings.push(solved.solution.timings);
+    }
+    Ok((BenchReport::from_timings(&timings, solved.solution.clone()), solved))
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Benchmark: {} runs", self.runs)?;
+        writeln!(f, "{:<6} {:>12} {:>12} {:>12} {:>12}", "phase", "min", "median", "mean", "max")?;
+        let phases = [("parse", &self.parse), ("part1", &self.part1), ("part2", &self.part2)];
+        for (name, stats) in phases.iter() {
+            writeln!(f, "{:<6} {:>12.3?} {:>12.3?} {:>12.3?} {:>12.3?}",
+                name, stats.min, stats.median, stats.mean, stats.max)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_stats() {
+        let ms = Duration::from_millis;
+        let stats = PhaseStats::from_samples(vec![ms(4), ms(1), ms(10), ms(3)]);
+        assert_eq!(stats, PhaseStats {
+            min: ms(1),
+            median: Duration::from_micros(3500),
+            mean: Duration::from_micros(4500),
+            max: ms(10),
+        });
+    }
+}
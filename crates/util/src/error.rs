@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/* Error from reading or parsing a puzzle input.  Parsers fill in
+ * as much of the location as they know (column within a line,
+ * line number, file) and the runners print it out like a compiler
+ * diagnostic.  Lines and columns count from 1; 0 means unknown.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    path: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    text: Option<String>,
+    reason: String,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(reason: S) -> ParseError {
+        ParseError {
+            path: None,
+            line: 0,
+            column: 0,
+            text: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at_column(mut self, column: usize) -> ParseError {
+        self.column = column;
+        self
+    }
+
+    /* Points the error at a field, which must be a slice of line. */
+    pub fn in_field(self, line: &str, field: &str) -> ParseError {
+        self.at_column(column_of(line, field))
+    }
+
+    /* Records which line the error is on, and the line's text so it
+     * can be quoted back in the diagnostic.
+     */
+    pub fn on_line(mut self, number: usize, text: &str) -> ParseError {
+        self.line = number;
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /* Records the file the error came from, unless it's already known. */
+    pub fn in_file(mut self, path: &Path) -> ParseError {
+        if self.path.is_none() {
+            self.path = Some(path.to_owned());
+        }
+        self
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "error: {}", self.reason)?;
+        let path = match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<input>".to_string(),
+        };
+        write!(f, "  --> {}", path)?;
+        if self.line == 0 {
+            return Ok(());
+        }
+        write!(f, ":{}", self.line)?;
+        if self.column != 0 {
+            write!(f, ":{}", self.column)?;
+        }
+
+        if let Some(text) = &self.text {
+            let gutter = self.line.to_string().len();
+            writeln!(f)?;
+            writeln!(f, "{:>w$} |", "", w = gutter)?;
+            write!(f, "{} | {}", self.line, text)?;
+            if self.column != 0 {
+                writeln!(f)?;
+                write!(f, "{:>w$} | {:>c$}", "", "^", w = gutter, c = self.column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/* 1-based column of field within line; falls back to the first
+ * column if field isn't actually part of line.
+ */
+fn column_of(line: &str, field: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = field.as_ptr() as usize;
+    if offset >= start && offset <= start + line.len() {
+        line[..offset - start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+/* Parses one field of a line, pointing at the field if it's bad. */
+pub fn parse_field<T>(line: &str, field: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    field.trim().parse::<T>().map_err(|e| {
+        ParseError::new(format!("{}: {:?}", e, field)).in_field(line, field)
+    })
+}
+
+/* Runs a single-line parser over every line, tagging any error
+ * with the line it came from.
+ */
+pub fn parse_lines<T, F>(lines: &[String], mut parser: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parser(line).map_err(|e| e.on_line(i + 1, line)))
+        .collect()
+}
+
+/* Opens an input file for one of the from_reader() parsers. */
+pub fn open(input: &Path) -> Result<BufReader<File>, ParseError> {
+    File::open(input)
+        .map(BufReader::new)
+        .map_err(|e| ParseError::new(format!("couldn't read input: {}", e)).in_file(input))
+}
+
+/* Reads an entire input into memory; "-" reads from stdin. */
+pub fn read_input(input: &Path) -> Result<Vec<u8>, ParseError> {
+    let mut data: Vec<u8> = Vec::new();
+    let result = if input == Path::new("-") {
+        io::stdin().lock().read_to_end(&mut data)
+    } else {
+        File::open(input).and_then(|mut f| f.read_to_end(&mut data))
+    };
+    match result {
+        Ok(_) => Ok(data),
+        Err(e) => Err(ParseError::new(format!("couldn't read input: {}", e)).in_file(input)),
+    }
+}
+
+/* Reads every line of an input. */
+pub fn read_lines<R: BufRead>(input: R) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.map_err(|e| ParseError {
+                line: i + 1,
+                ..ParseError::new(format!("couldn't read input: {}", e))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_display() {
+        let line = "8,0 -> 8,x";
+        let err = parse_field::<i32>(line, &line[9..])
+            .unwrap_err()
+            .on_line(12, line)
+            .in_file(Path::new("data/vents.txt"));
+        assert_eq!(err.to_string(), concat!(
+            "error: invalid digit found in string: \"x\"\n",
+            "  --> data/vents.txt:12:10\n",
+            "   |\n",
+            "12 | 8,0 -> 8,x\n",
+            "   |          ^"
+        ));
+    }
+
+    #[test]
+    fn test_read_lines_from_reader() {
+        let input: &[u8] = b"0,9 -> 5,9\n8,0 -> 0,8\n";
+        assert_eq!(read_lines(input).unwrap(), vec!["0,9 -> 5,9", "8,0 -> 0,8"]);
+    }
+}
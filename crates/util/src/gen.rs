@@ -0,0 +1,66 @@
+use std::env;
+
+/* Helpers for generating synthetic puzzle inputs, so the benchmarks
+ * can see how each solver scales past the official input size.
+ */
+
+/* Input sizes to generate, as multiples of the official input. */
+pub const SCALES: &[usize] = &[1, 10, 100, 1000];
+
+/* Scales the benchmarks should run.  The 1000x inputs take minutes
+ * for some days, so they're skipped unless AOC_BENCH_MAX_SCALE is
+ * raised from its default of 100.
+ */
+pub fn bench_scales() -> Vec<usize> {
+    let max_scale = env::var("AOC_BENCH_MAX_SCALE")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(100);
+    SCALES.iter().copied().filter(|&s| s <= max_scale).collect()
+}
+
+/* Small xorshift PRNG, so generated inputs are repeatable run to
+ * run without pulling in a dependency.
+ */
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        /* xorshift gets stuck at zero, so nudge the seed off it. */
+        Rng { state: seed ^ 0x9e37_79b9_7f4a_7c15 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /* Uniform value in [0, n). */
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /* Uniform value in [lo, hi]. */
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo + 1) as u64) as i64
+    }
+
+    /* True with probability numerator/denominator. */
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,450 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/* Rectangular 2D grid of cells stored row by row.  Cells are
+ * addressed as (x, y), with (0, 0) the top left corner of the
+ * input it was parsed from.
+ */
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/* Offsets to the neighbors of a cell: the first four are the
+ * cardinal directions (N, E, S, W), then the diagonals.
+ */
+const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (0, -1), (1, 0), (0, 1), (-1, 0),
+    (1, -1), (1, 1), (-1, 1), (-1, -1),
+];
+
+/* Which cells count as neighbors: the 4 sharing an edge, or
+ * all 8 touching, diagonals included.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    #[default]
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &NEIGHBOR_OFFSETS[..4],
+            Connectivity::Eight => &NEIGHBOR_OFFSETS,
+        }
+    }
+}
+
+impl FromStr for Connectivity {
+    type Err = String;
+
+    fn from_str(connectivity: &str) -> Result<Connectivity, String> {
+        match connectivity {
+            "4" => Ok(Connectivity::Four),
+            "8" => Ok(Connectivity::Eight),
+            _ => Err(format!("connectivity must be 4 or 8, not {:?}", connectivity)),
+        }
+    }
+}
+
+impl fmt::Display for Connectivity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Connectivity::Four => write!(f, "4"),
+            Connectivity::Eight => write!(f, "8"),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /* Grid of width x height cells, all set to fill. */
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /* Grid from cells laid out row by row; panics if there
+     * aren't exactly width x height of them.
+     */
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid is {}x{}", width, height);
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /* Parses one row of the grid from each line; every row must be
+     * as wide as the first.  first_line is the line number of the
+     * first row, for error reporting, which takes cells to be
+     * separated by commas or whitespace.
+     */
+    pub fn parse_rows<F>(lines: &[String], first_line: usize, parse_row: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(&str) -> Result<Vec<T>, ParseError>,
+    {
+        let rows = parse_row_vecs(lines, first_line, parse_row)?;
+        Grid::rectangular(rows, lines, first_line, field_column)
+    }
+
+    /* parse_rows(), but rows narrower than the widest are padded
+     * out on the right with fill rather than rejected.
+     */
+    pub fn parse_rows_padded<F>(lines: &[String], first_line: usize, fill: T, parse_row: F) -> Result<Grid<T>, ParseError>
+    where
+        T: Clone,
+        F: FnMut(&str) -> Result<Vec<T>, ParseError>,
+    {
+        let rows = parse_row_vecs(lines, first_line, parse_row)?;
+        Ok(Grid::padded(rows, fill))
+    }
+
+    /* Parses a grid with one character per cell. */
+    pub fn parse<F>(lines: &[String], parse_cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let rows = parse_char_rows(lines, parse_cell)?;
+        Grid::rectangular(rows, lines, 1, |_, cell| cell + 1)
+    }
+
+    /* parse(), padding narrow rows out with fill. */
+    pub fn parse_padded<F>(lines: &[String], fill: T, parse_cell: F) -> Result<Grid<T>, ParseError>
+    where
+        T: Clone,
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let rows = parse_char_rows(lines, parse_cell)?;
+        Ok(Grid::padded(rows, fill))
+    }
+
+    /* Grid from rows that must all be as wide as the first.  An
+     * empty row (a blank line) is reported on its own line, even if
+     * it comes first; otherwise a short row is reported at the end
+     * of its line, and a long one at its first extra cell, if
+     * cell_column(line, n) knows which column the nth cell starts at
+     * (0 if it doesn't).
+     */
+    fn rectangular<C>(rows: Vec<Vec<T>>, lines: &[String], first_line: usize, cell_column: C) -> Result<Grid<T>, ParseError>
+    where
+        C: Fn(&str, usize) -> usize,
+    {
+        let width = rows[0].len();
+        for (i, (row, line)) in rows.iter().zip(lines).enumerate() {
+            if row.is_empty() {
+                return Err(ParseError::new("empty row in grid")
+                    .at_column(1)
+                    .on_line(first_line + i, line));
+            }
+            if row.len() != width {
+                let column = if row.len() < width {
+                    line.trim_end().chars().count() + 1
+                } else {
+                    cell_column(line, width)
+                };
+                return Err(ParseError::new(format!("expected {} cells in row, found {}", width, row.len()))
+                    .at_column(column)
+                    .on_line(first_line + i, line));
+            }
+        }
+        let height = rows.len();
+        Ok(Grid::from_vec(width, height, rows.into_iter().flatten().collect()))
+    }
+
+    /* Grid as wide as the widest row, the rest filled out. */
+    fn padded(rows: Vec<Vec<T>>, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for row in rows {
+            let missing = width - row.len();
+            cells.extend(row);
+            /* Not repeat_n(), which needs Rust 1.82. */
+            #[allow(clippy::manual_repeat_n)]
+            cells.extend(std::iter::repeat(fill.clone()).take(missing));
+        }
+        Grid::from_vec(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /* Every cell, row by row. */
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /* Coordinates of every cell, row by row. */
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} of {}", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /* Coordinates of the cells around (x, y) that are on the grid. */
+    pub fn neighbors(&self, x: usize, y: usize, connectivity: Connectivity) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            if nx < width && ny < height {
+                Some((nx, ny))
+            } else {
+                None
+            }
+        })
+    }
+
+    /* Coordinates of the up to 4 cells sharing an edge with (x, y). */
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(x, y, Connectivity::Four)
+    }
+
+    /* Coordinates of the up to 8 cells touching (x, y), diagonals included. */
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(x, y, Connectivity::Eight)
+    }
+
+    /* Grid with rows and columns swapped. */
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width).flat_map(|x| self.column(x).cloned()).collect();
+        Grid::from_vec(self.height, self.width, cells)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+/* Parses each line into a row, with any error tagged with its
+ * line.  Fails if there are no cells at all.
+ */
+fn parse_row_vecs<T, F>(lines: &[String], first_line: usize, mut parse_row: F) -> Result<Vec<Vec<T>>, ParseError>
+where
+    F: FnMut(&str) -> Result<Vec<T>, ParseError>,
+{
+    let rows = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_row(line).map_err(|e| e.on_line(first_line + i, line)))
+        .collect::<Result<Vec<Vec<T>>, ParseError>>()?;
+    if rows.iter().all(Vec::is_empty) {
+        return Err(ParseError::new("no grid in input"));
+    }
+    Ok(rows)
+}
+
+/* Column at which the nth field of a line starts (counting from
+ * 0), fields being separated by commas or whitespace, or 0 if there
+ * aren't that many.
+ */
+fn field_column(line: &str, n: usize) -> usize {
+    let mut fields = 0;
+    let mut in_field = false;
+    for (i, c) in line.chars().enumerate() {
+        let separator = c == ',' || c.is_whitespace();
+        if !separator && !in_field {
+            if fields == n {
+                return i + 1;
+            }
+            fields += 1;
+        }
+        in_field = !separator;
+    }
+    0
+}
+
+/* Rows of one cell per character, from line 1. */
+fn parse_char_rows<T, F>(lines: &[String], mut parse_cell: F) -> Result<Vec<Vec<T>>, ParseError>
+where
+    F: FnMut(char) -> Result<T, String>,
+{
+    parse_row_vecs(lines, 1, |line| {
+        line.chars()
+            .enumerate()
+            .map(|(i, c)| parse_cell(c).map_err(|e| ParseError::new(e).at_column(i + 1)))
+            .collect()
+    })
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.get(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside the {}x{} grid", x, y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside the {}x{} grid", x, y, width, height),
+        }
+    }
+}
+
+/* One line per row.  Cells are written back to back, each padded
+ * to the width given in the format string, if any: "{:3}".
+ */
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(lines: &[&str]) -> Result<Grid<u32>, ParseError> {
+        let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        Grid::parse(&lines, |c| c.to_digit(10).ok_or(format!("bad digit {:?}", c)))
+    }
+
+    #[test]
+    fn test_grid_parse() {
+        let grid = digits(&["123", "456"]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(format!("{:2}", grid), " 1 2 3\n 4 5 6\n");
+
+        let err = digits(&["123", "4x6"]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        let err = digits(&["123", "45"]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        let err = digits(&["123", "4567"]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert!(digits(&[]).unwrap_err().to_string().starts_with("error: no grid in input"));
+        assert!(digits(&["", ""]).is_err());
+
+        let err = digits(&["", "123", "456"]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 1));
+        assert!(err.to_string().starts_with("error: empty row in grid"));
+        let err = digits(&["123", "", "456"]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
+    }
+
+    #[test]
+    fn test_grid_parse_rows() {
+        let lines: Vec<String> = ["1, 2,3", "4,5,  6, 7", "8,9"].iter().map(|l| l.to_string()).collect();
+        let fields = |line: &str| -> Result<Vec<u32>, ParseError> {
+            line.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .map(|field| crate::parse_field(line, field))
+                .collect()
+        };
+        let err = Grid::parse_rows(&lines[..2], 1, fields).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 10));
+        let err = Grid::parse_rows(&[lines[0].clone(), lines[2].clone()], 5, fields).unwrap_err();
+        assert_eq!((err.line(), err.column()), (6, 4));
+        assert_eq!(field_column(" 10  20", 1), 6);
+        assert_eq!(field_column("10", 1), 0);
+    }
+
+    #[test]
+    fn test_grid_parse_padded() {
+        let lines: Vec<String> = ["12", "3456", "", "7"].iter().map(|l| l.to_string()).collect();
+        let grid = Grid::parse_padded(&lines, 0, |c| c.to_digit(10).ok_or(format!("bad digit {:?}", c))).unwrap();
+        assert_eq!(grid.to_string(), "1200\n3456\n0000\n7000\n");
+    }
+
+    #[test]
+    fn test_grid_rows_columns() {
+        let grid = digits(&["123", "456"]).unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        let columns: Vec<Vec<u32>> = grid.columns().map(|c| c.cloned().collect()).collect();
+        assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = digits(&["123", "456", "789"]).unwrap();
+        assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors8(2, 2).collect::<Vec<_>>(), [(2, 1), (1, 2), (1, 1)]);
+    }
+}
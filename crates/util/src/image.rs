@@ -0,0 +1,144 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::Grid;
+
+/* Red, green and blue, 0-255. */
+pub type Rgb = [u8; 3];
+
+/* Control points for colormap(), sampled from matplotlib's viridis:
+ * dark purple through blue and green to yellow, and readable in
+ * grayscale too.
+ */
+const VIRIDIS: [Rgb; 5] = [
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+
+/* Gray of the given brightness. */
+pub fn gray(level: u8) -> Rgb {
+    [level, level, level]
+}
+
+/* Color for t between 0.0 (low) and 1.0 (high). */
+pub fn colormap(t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0) * (VIRIDIS.len() - 1) as f64;
+    let i = (t as usize).min(VIRIDIS.len() - 2);
+    let frac = t - i as f64;
+    let (a, b) = (VIRIDIS[i], VIRIDIS[i + 1]);
+    let mut rgb = [0; 3];
+    for c in 0..3 {
+        rgb[c] = (a[c] as f64 + (b[c] as f64 - a[c] as f64) * frac).round() as u8;
+    }
+    rgb
+}
+
+/* The nth of a run of colors picked to look different from their
+ * neighbors in the run, for telling regions apart.  Hues step round
+ * the color wheel by the golden angle.
+ */
+pub fn distinct_color(n: usize) -> Rgb {
+    let hue = (n as f64 * 0.618_033_988_75).fract() * 6.0;
+    let (saturation, value) = (0.65, 0.95);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    [channel(r), channel(g), channel(b)]
+}
+
+/* Darkens (factor < 1.0) or keeps a color. */
+pub fn shade(rgb: Rgb, factor: f64) -> Rgb {
+    let factor = factor.clamp(0.0, 1.0);
+    [
+        (rgb[0] as f64 * factor).round() as u8,
+        (rgb[1] as f64 * factor).round() as u8,
+        (rgb[2] as f64 * factor).round() as u8,
+    ]
+}
+
+/* Blows an image up by an integer factor, each pixel becoming a
+ * factor x factor square, so small grids are visible.
+ */
+pub fn upscale(image: &Grid<Rgb>, factor: usize) -> Grid<Rgb> {
+    let factor = factor.max(1);
+    let (width, height) = (image.width() * factor, image.height() * factor);
+    let pixels = (0..width * height)
+        .map(|i| image[((i % width) / factor, (i / width) / factor)])
+        .collect();
+    Grid::from_vec(width, height, pixels)
+}
+
+/* Writes a binary (P6) PPM. */
+pub fn write_ppm<W: Write>(image: &Grid<Rgb>, mut out: W) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    for pixel in image.iter() {
+        out.write_all(pixel)?;
+    }
+    out.flush()
+}
+
+/* Writes an 8-bit RGB PNG. */
+pub fn write_png<W: Write>(image: &Grid<Rgb>, out: W) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    let data: Vec<u8> = image.iter().flatten().copied().collect();
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/* Saves an image as PNG or PPM, going by the file's extension. */
+pub fn save_image(image: &Grid<Rgb>, path: &Path) -> io::Result<()> {
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => write_png(image, BufWriter::new(File::create(path)?)),
+        Some("ppm") => write_ppm(image, BufWriter::new(File::create(path)?)),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "images must be .png or .ppm")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_ppm() {
+        let image = Grid::from_vec(2, 1, vec![gray(0), [255, 0, 10]]);
+        let mut out: Vec<u8> = Vec::new();
+        write_ppm(&upscale(&image, 2), &mut out).unwrap();
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+        for _ in 0..2 {
+            expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 255, 0, 10, 255, 0, 10]);
+        }
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let image = Grid::new(3, 2, colormap(0.5));
+        let mut out: Vec<u8> = Vec::new();
+        write_png(&image, &mut out).unwrap();
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut reader = png::Decoder::new(&out[..]).read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&data[..3], &VIRIDIS[2]);
+    }
+}
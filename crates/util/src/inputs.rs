@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgMatches};
+use toml::Value;
+
+use crate::{read_input, ParseError, Puzzle};
+
+/* Name of the manifest of checksums kept alongside the inputs. */
+const MANIFEST: &str = "inputs.toml";
+
+/* What's known about an imported input. */
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    name: String,
+    checksum: u64,
+}
+
+/* State of one puzzle's input in the store. */
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputStatus {
+    Ok,
+    Missing,
+    /* Present, but never imported, so there's no checksum to check. */
+    Untracked,
+    /* Doesn't match the checksum recorded when it was imported. */
+    Corrupt { expected: u64, found: u64 },
+    /* The source has a different copy than the one imported. */
+    Stale { source: u64 },
+}
+
+/* Local store of puzzle inputs, laid out as <dir>/<name>.txt with
+ * a manifest recording each input's checksum when it was imported.
+ * Missing inputs can be fetched from a source directory standing
+ * in for the website, laid out like its URLs: <source>/day/<N>/input.
+ * Nothing here touches the network.
+ */
+#[derive(Clone, Debug)]
+pub struct InputStore {
+    dir: PathBuf,
+    source: Option<PathBuf>,
+    manifest: BTreeMap<u32, Entry>,
+}
+
+/* 64-bit FNV-1a hash of an input. */
+pub fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn io_error(what: &str, path: &Path, e: io::Error) -> ParseError {
+    ParseError::new(format!("couldn't {}: {}", what, e)).in_file(path)
+}
+
+impl InputStore {
+    /* Opens the store in dir, which needn't exist yet. */
+    pub fn open(dir: &Path, source: Option<&Path>) -> Result<InputStore, ParseError> {
+        let path = dir.join(MANIFEST);
+        let manifest = match fs::read_to_string(&path) {
+            Ok(text) => InputStore::parse_manifest(&text).map_err(|e| e.in_file(&path))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(io_error("read input manifest", &path, e)),
+        };
+        Ok(InputStore {
+            dir: dir.to_owned(),
+            source: source.map(Path::to_owned),
+            manifest,
+        })
+    }
+
+    fn parse_manifest(text: &str) -> Result<BTreeMap<u32, Entry>, ParseError> {
+        let table = match text.parse::<Value>() {
+            Ok(Value::Table(table)) => table,
+            Ok(_) => return Err(ParseError::new("expected a table of days")),
+            Err(e) => return Err(ParseError::new(e.to_string())),
+        };
+
+        let mut manifest = BTreeMap::new();
+        for (key, entry) in table {
+            let day = match key.strip_prefix("day").and_then(|d| d.parse::<u32>().ok()) {
+                Some(day) => day,
+                None => return Err(ParseError::new(format!("expected [dayN] section, found [{}]", key))),
+            };
+            let name = entry.get("name").and_then(Value::as_str);
+            let checksum = entry.get("checksum")
+                .and_then(Value::as_str)
+                .and_then(|c| u64::from_str_radix(c, 16).ok());
+            match (name, checksum) {
+                (Some(name), Some(checksum)) => {
+                    manifest.insert(day, Entry { name: name.to_string(), checksum });
+                },
+                _ => return Err(ParseError::new(format!("[{}] needs a name and a hex checksum", key))),
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn save(&self) -> Result<(), ParseError> {
+        let mut text = String::from("# Checksums of imported puzzle inputs; written by `aoc inputs`.\n");
+        for (day, entry) in &self.manifest {
+            text += &format!("\n[day{}]\nname = {:?}\nchecksum = \"{:016x}\"\n", day, entry.name, entry.checksum);
+        }
+        let path = self.dir.join(MANIFEST);
+        fs::write(&path, text).map_err(|e| io_error("write input manifest", &path, e))
+    }
+
+    /* Where a puzzle's input lives in the store. */
+    pub fn path(&self, puzzle: &Puzzle) -> PathBuf {
+        self.dir.join(format!("{}.txt", puzzle.name))
+    }
+
+    /* Where a puzzle's input lives in the source, if there is one. */
+    pub fn source_path(&self, puzzle: &Puzzle) -> Option<PathBuf> {
+        self.source.as_ref().map(|source| source.join(format!("day/{}/input", puzzle.day)))
+    }
+
+    fn read(path: &Path) -> Result<Option<Vec<u8>>, ParseError> {
+        match fs::read(path) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read input", path, e)),
+        }
+    }
+
+    /* Checksum of a puzzle's input as it is now, if it's present. */
+    pub fn checksum(&self, puzzle: &Puzzle) -> Result<Option<u64>, ParseError> {
+        Ok(InputStore::read(&self.path(puzzle))?.map(|data| checksum(&data)))
+    }
+
+    /* Checks a puzzle's input against its recorded checksum and,
+     * if there's a source, against the source's copy.
+     */
+    pub fn status(&self, puzzle: &Puzzle) -> Result<InputStatus, ParseError> {
+        let found = match self.checksum(puzzle)? {
+            Some(found) => found,
+            None => return Ok(InputStatus::Missing),
+        };
+        match self.manifest.get(&puzzle.day) {
+            None => return Ok(InputStatus::Untracked),
+            Some(entry) if entry.checksum != found => {
+                return Ok(InputStatus::Corrupt { expected: entry.checksum, found });
+            },
+            Some(_) => {},
+        }
+        if let Some(source) = self.source_path(puzzle) {
+            if let Some(data) = InputStore::read(&source)? {
+                let source = checksum(&data);
+                if source != found {
+                    return Ok(InputStatus::Stale { source });
+                }
+            }
+        }
+        Ok(InputStatus::Ok)
+    }
+
+    /* Copies an input ("-" for stdin) into the store and records its
+     * checksum.  The input must parse, so a file meant for another
+     * day gets caught here rather than when it's solved.
+     */
+    pub fn import(&mut self, puzzle: &Puzzle, from: &Path) -> Result<u64, ParseError> {
+        let data = read_input(from)?;
+        (puzzle.solve)(&data, None).map_err(|e| e.in_file(from))?;
+
+        let path = self.path(puzzle);
+        fs::create_dir_all(&self.dir).map_err(|e| io_error("create input store", &self.dir, e))?;
+        fs::write(&path, &data).map_err(|e| io_error("write input", &path, e))?;
+        let checksum = checksum(&data);
+        self.manifest.insert(puzzle.day, Entry { name: puzzle.name.to_string(), checksum });
+        self.save()?;
+        Ok(checksum)
+    }
+
+    /* Imports a puzzle's input from the source. */
+    pub fn fetch(&mut self, puzzle: &Puzzle) -> Result<u64, ParseError> {
+        match self.source_path(puzzle) {
+            Some(source) => self.import(puzzle, &source),
+            None => Err(ParseError::new(format!("no --source to fetch day {} from", puzzle.day))),
+        }
+    }
+
+    /* Finds the input to use for a puzzle when none was given,
+     * fetching it from the source if it's missing.  Corrupt inputs
+     * are refused; stale ones are used with a warning.
+     */
+    pub fn resolve(&mut self, puzzle: &Puzzle) -> Result<PathBuf, ParseError> {
+        let path = self.path(puzzle);
+        match self.status(puzzle)? {
+            InputStatus::Ok | InputStatus::Untracked => {},
+            InputStatus::Missing if self.source.is_some() => {
+                self.fetch(puzzle)?;
+                eprintln!("Fetched day {} input into {}", puzzle.day, path.display());
+            },
+            InputStatus::Missing => {
+                return Err(ParseError::new(format!(
+                    "no input for day {}; add one with `aoc inputs import --day {} FILE`",
+                    puzzle.day, puzzle.day)).in_file(&path));
+            },
+            status @ InputStatus::Corrupt { .. } => {
+                return Err(ParseError::new(format!("input is {}; import it again", status)).in_file(&path));
+            },
+            status @ InputStatus::Stale { .. } => {
+                eprintln!("warning: {} is {}", path.display(), status);
+            },
+        }
+        Ok(path)
+    }
+}
+
+impl fmt::Display for InputStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputStatus::Ok => write!(f, "ok"),
+            InputStatus::Missing => write!(f, "missing"),
+            InputStatus::Untracked => write!(f, "untracked"),
+            InputStatus::Corrupt { expected, .. } => write!(f, "corrupt (expected {:016x})", expected),
+            InputStatus::Stale { source } => write!(f, "stale (source has {:016x})", source),
+        }
+    }
+}
+
+/* Command line options for the input store, shared by every binary. */
+pub fn store_args<'help>() -> Vec<Arg<'help>> {
+    vec![
+        Arg::new("data")
+            .about("Directory holding the <name>.txt puzzle inputs")
+            .long("data")
+            .value_name("DIR")
+            .takes_value(true)
+            .default_value("data"),
+        Arg::new("source")
+            .about("Directory standing in for the website to fetch missing inputs from (day/<N>/input)")
+            .long("source")
+            .value_name("DIR")
+            .takes_value(true),
+    ]
+}
+
+/* Opens the input store named by store_args(), exiting with an
+ * error message if its manifest is bad.
+ */
+pub fn input_store(argmatches: &ArgMatches) -> InputStore {
+    let dir = Path::new(argmatches.value_of("data").unwrap_or("data"));
+    let source = argmatches.value_of("source").map(Path::new);
+    InputStore::open(dir, source).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solution, Timings};
+
+    /* Stand-in puzzle whose input is any text that isn't "bad". */
+    const PUZZLE: Puzzle = Puzzle {
+        day: 5,
+        title: "Hydrothermal Venture",
+        name: "vents",
+        args: Vec::new,
+        solve: |input, _| {
+            if input == b"bad" {
+                return Err(ParseError::new("bad input"));
+            }
+            Ok(Solution { day: 5, part1: String::new(), part2: String::new(), timings: Timings::default() }.into())
+        },
+    };
+
+    fn scratch_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0xcbf29ce484222325);
+        assert_eq!(checksum(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_input_store_import() {
+        let dir = scratch_dir("import");
+        let input = dir.join("download.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&input, "0,9 -> 5,9\n").unwrap();
+
+        let mut store = InputStore::open(&dir.join("data"), None).unwrap();
+        assert_eq!(store.status(&PUZZLE).unwrap(), InputStatus::Missing);
+        assert!(store.resolve(&PUZZLE).is_err());
+        store.import(&PUZZLE, &input).unwrap();
+
+        let store = InputStore::open(&dir.join("data"), None).unwrap();
+        assert_eq!(store.status(&PUZZLE).unwrap(), InputStatus::Ok);
+        fs::write(store.path(&PUZZLE), "0,9 -> 5,\n").unwrap();
+        assert!(matches!(store.status(&PUZZLE).unwrap(), InputStatus::Corrupt { .. }));
+
+        fs::write(&input, "bad").unwrap();
+        assert!(InputStore::open(&dir.join("data"), None).unwrap().import(&PUZZLE, &input).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_store_source() {
+        let dir = scratch_dir("source");
+        let source = dir.join("website");
+        fs::create_dir_all(source.join("day/5")).unwrap();
+        fs::write(source.join("day/5/input"), "0,9 -> 5,9\n").unwrap();
+
+        let mut store = InputStore::open(&dir.join("data"), Some(&source)).unwrap();
+        assert_eq!(store.resolve(&PUZZLE).unwrap(), dir.join("data/vents.txt"));
+        assert_eq!(store.status(&PUZZLE).unwrap(), InputStatus::Ok);
+
+        fs::write(source.join("day/5/input"), "8,0 -> 0,8\n").unwrap();
+        assert!(matches!(store.status(&PUZZLE).unwrap(), InputStatus::Stale { .. }));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::{BenchReport, PhaseStats, Puzzle, Solution, Verdict};
+
+/* Just enough JSON writing for the --format json output; every
+ * object is built up as a string, one field at a time.
+ */
+struct JsonObject {
+    fields: Vec<String>,
+}
+
+impl JsonObject {
+    fn new() -> JsonObject {
+        JsonObject { fields: Vec::new() }
+    }
+
+    /* Adds a field whose value is already valid JSON. */
+    fn raw(mut self, key: &str, value: String) -> JsonObject {
+        self.fields.push(format!("{}:{}", quote(key), value));
+        self
+    }
+
+    fn string(self, key: &str, value: &str) -> JsonObject {
+        self.raw(key, quote(value))
+    }
+
+    fn nanos(self, key: &str, value: Duration) -> JsonObject {
+        self.raw(key, value.as_nanos().to_string())
+    }
+
+    fn build(self) -> String {
+        format!("{{{}}}", self.fields.join(","))
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/* Answers that look like integers are written as JSON numbers,
+ * anything else as a string.
+ */
+fn answer(s: &str) -> String {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        s.to_string()
+    } else {
+        quote(s)
+    }
+}
+
+fn phase_stats(stats: &PhaseStats) -> String {
+    JsonObject::new()
+        .nanos("min_ns", stats.min)
+        .nanos("median_ns", stats.median)
+        .nanos("mean_ns", stats.mean)
+        .nanos("max_ns", stats.max)
+        .build()
+}
+
+/* One puzzle's results as a single-line JSON object, eg:
+ *
+ *   {"day":5,"title":"Hydrothermal Venture","input":"data/vents.txt",
+ *    "part1":5294,"part2":21698,
+ *    "timings":{"parse_ns":958575,"part1_ns":149145000,"part2_ns":289273000}}
+ *
+ * with "verify" and "bench" objects added when those modes are on.
+ */
+pub fn solution_json(
+    puzzle: &Puzzle,
+    input: &Path,
+    solution: &Solution,
+    verdicts: Option<&[Verdict; 2]>,
+    report: Option<&BenchReport>,
+) -> String {
+    let timings = JsonObject::new()
+        .nanos("parse_ns", solution.timings.parse)
+        .nanos("part1_ns", solution.timings.part1)
+        .nanos("part2_ns", solution.timings.part2)
+        .build();
+    let mut object = JsonObject::new()
+        .raw("day", solution.day.to_string())
+        .string("title", puzzle.title)
+        .string("input", &input.display().to_string())
+        .raw("part1", answer(&solution.part1))
+        .raw("part2", answer(&solution.part2))
+        .raw("timings", timings);
+
+    if let Some(verdicts) = verdicts {
+        let verify = JsonObject::new()
+            .string("part1", &verdicts[0].to_string())
+            .string("part2", &verdicts[1].to_string())
+            .build();
+        object = object.raw("verify", verify);
+    }
+
+    if let Some(report) = report {
+        let bench = JsonObject::new()
+            .raw("runs", report.runs.to_string())
+            .raw("parse", phase_stats(&report.parse))
+            .raw("part1", phase_stats(&report.part1))
+            .raw("part2", phase_stats(&report.part2))
+            .build();
+        object = object.raw("bench", bench);
+    }
+    object.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Timings;
+
+    #[test]
+    fn test_solution_json() {
+        let puzzle = Puzzle {
+            day: 9,
+            title: "Smoke \"Basin\"",
+            name: "smokebasin",
+            args: Vec::new,
+            solve: |_, _| unreachable!(),
+        };
+        let solution = Solution {
+            day: 9,
+            part1: "518".to_string(),
+            part2: "unsolved".to_string(),
+            timings: Timings {
+                parse: Duration::from_nanos(10),
+                part1: Duration::from_nanos(20),
+                part2: Duration::from_nanos(30),
+            },
+        };
+        assert_eq!(
+            solution_json(&puzzle, Path::new("<stdin>"), &solution, Some(&[Verdict::Pass, Verdict::Missing]), None),
+            concat!(
+                r#"{"day":9,"title":"Smoke \"Basin\"","input":"<stdin>","part1":518,"part2":"unsolved","#,
+                r#""timings":{"parse_ns":10,"part1_ns":20,"part2_ns":30},"#,
+                r#""verify":{"part1":"PASS","part2":"SKIP"}}"#
+            )
+        );
+    }
+}
@@ -1,28 +1,95 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use clap::{App, Arg};
 
+mod bench;
+mod error;
+pub mod gen;
+mod grid;
+pub mod image;
+mod inputs;
+mod json;
+mod runner;
+mod solver;
+mod unionfind;
+mod verify;
+
+pub use bench::{bench, BenchReport, PhaseStats};
+pub use error::{open, parse_field, parse_lines, read_input, read_lines, ParseError};
+pub use grid::{Connectivity, Grid};
+pub use inputs::{checksum, input_store, store_args, InputStatus, InputStore};
+pub use runner::{run_args, run_inputs, run_options, run_puzzle, Format, RunOptions};
+pub use solver::{solve, Puzzle, Solution, Solved, Solver, Timings};
+pub use unionfind::UnionFind;
+pub use verify::{Answers, Verdict};
+
+/* Options from the standard puzzle command line. */
+#[derive(Clone, Debug)]
+pub struct AdventArgs {
+    /* Empty if none were given, meaning use the input store. */
+    pub inputs: Vec<PathBuf>,
+    pub store: InputStore,
+    pub options: RunOptions,
+}
+
+/* The --help description of a puzzle's binary.  Puzzle options
+ * are Arg<'static>, which makes the whole App<'static>, so the text
+ * has to live for the rest of the process: it's built once and
+ * leaked on purpose, the one leak in the command line handling.
+ */
+fn about_text(puzzle: &Puzzle) -> &'static str {
+    let about = format!("Advent of Code 2021 - Day {}: {}", puzzle.day, puzzle.title);
+    Box::leak(about.into_boxed_str())
+}
+
 /* Provides the standard CLI I'm using for most of the
- * 2021 Advent-of-Code puzzles.  Returns a path to the
- * test*/
-pub fn advent_cli(puzzle_name: &str, day: u32) -> PathBuf {
-     let title = puzzle_name;
-     let about = format!("Advent of Code 2021 - Day {}: {}", day, title).to_owned();
+ * 2021 Advent-of-Code puzzles.  Returns the paths to the
+ * inputs ("-" for stdin) along with any other options given.
+ */
+pub fn advent_cli(puzzle: &Puzzle) -> AdventArgs {
+     let title = puzzle.title;
      let argmatches = App::new(title)
         .version("0.1")
         .author("Jack Bradach <jack@bradach.net>")
-        .about(&about[..])
+        .about(about_text(puzzle))
         .arg(Arg::new("input")
-            .about("Input dataset from website")
+            .about("Input datasets from website; - reads stdin (defaults to the data directory)")
             .index(1)
-            .required(true))
+            .multiple_values(true))
+        .args(store_args())
+        .args(run_args())
+        .args((puzzle.args)())
         .get_matches();
 
-    let input = match argmatches.value_of("input") {
-        Some(input) => Path::new(input),
-        None => {
-            eprintln!("No input data file specified!");
-            std::process::exit(1);
-        }
+    let inputs: Vec<PathBuf> = match argmatches.values_of("input") {
+        Some(inputs) => inputs.map(PathBuf::from).collect(),
+        None => Vec::new(),
     };
-    input.to_owned()
+    AdventArgs {
+        inputs,
+        store: input_store(&argmatches),
+        options: run_options(&argmatches),
+    }
+}
+
+/* Standard main() for a single puzzle binary: parse the
+ * command line, then solve and print both parts, using the
+ * input from the store if none was given.  Bad input
+ * is reported as a diagnostic and exits non-zero, as does
+ * a wrong answer when verifying.
+ */
+pub fn advent_main<S: Solver + 'static>() {
+    let puzzle = Puzzle::of::<S>();
+    let mut args = advent_cli(&puzzle);
+    if args.inputs.is_empty() {
+        match args.store.resolve(&puzzle) {
+            Ok(input) => args.inputs.push(input),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if !run_inputs(&puzzle, &args.inputs, &args.options) {
+        std::process::exit(1);
+    }
 }
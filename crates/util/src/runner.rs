@@ -0,0 +1,185 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use clap::{Arg, ArgMatches};
+
+use crate::json::solution_json;
+use crate::{bench, read_input, Answers, Puzzle, Verdict};
+
+/* How results are written to stdout. */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown output format {:?}", format)),
+        }
+    }
+}
+
+/* What to do with a puzzle's answers once it's solved. */
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /* Check the answers against these rather than printing them. */
+    pub answers: Option<Answers>,
+    /* Solve this many times and print timing statistics. */
+    pub bench: Option<usize>,
+    pub format: Format,
+    /* The whole command line, for puzzles with options of their own. */
+    pub argmatches: Option<ArgMatches>,
+}
+
+/* Command line options for RunOptions, shared by every binary. */
+pub fn run_args<'help>() -> Vec<Arg<'help>> {
+    vec![
+        Arg::new("verify")
+            .about("Check the answers against a TOML file of expected answers")
+            .long("verify")
+            .value_name("ANSWERS")
+            .takes_value(true),
+        Arg::new("bench")
+            .about("Solve N times and report min/median/mean/max time for each phase")
+            .long("bench")
+            .value_name("N")
+            .takes_value(true),
+        Arg::new("format")
+            .about("Output format")
+            .long("format")
+            .takes_value(true)
+            .possible_values(["text", "json"])
+            .default_value("text"),
+    ]
+}
+
+/* Builds RunOptions from the matches for run_args(), exiting with
+ * an error message if any of them are bad.
+ */
+pub fn run_options(argmatches: &ArgMatches) -> RunOptions {
+    let answers = argmatches.value_of("verify").map(|path| {
+        match Answers::from_file(Path::new(path)) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    });
+    let bench = argmatches.value_of("bench").map(|runs| {
+        match runs.parse::<usize>() {
+            Ok(runs) if runs > 0 => runs,
+            _ => {
+                eprintln!("--bench needs a run count of at least 1, not {:?}", runs);
+                std::process::exit(1);
+            }
+        }
+    });
+    let format = argmatches.value_of("format")
+        .map_or(Ok(Format::Text), Format::from_str)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+    RunOptions {
+        answers,
+        bench,
+        format,
+        argmatches: Some(argmatches.clone()),
+    }
+}
+
+/* How an input is named in output and diagnostics. */
+fn input_name(input: &Path) -> PathBuf {
+    if input == Path::new("-") {
+        PathBuf::from("<stdin>")
+    } else {
+        input.to_owned()
+    }
+}
+
+/* Solves a puzzle and prints (or verifies) its answers, followed
+ * by benchmark timings if asked for, then exports anything else
+ * the puzzle's own options ask for.  The input is read once up
+ * front, so "-" (stdin) works with --bench too.  Returns false if
+ * the input was bad or an answer was wrong.
+ */
+pub fn run_puzzle(puzzle: &Puzzle, input: &Path, options: &RunOptions) -> bool {
+    let name = input_name(input);
+    let data = match read_input(input) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("{}", e.in_file(&name));
+            return false;
+        }
+    };
+    let solve = |data: &[u8]| (puzzle.solve)(data, options.argmatches.as_ref());
+    let result = match options.bench {
+        Some(runs) => bench(solve, &data, runs)
+            .map(|(report, solved)| (solved, Some(report))),
+        None => solve(&data).map(|solved| (solved, None)),
+    };
+    let (solved, report) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("{}", e.in_file(&name));
+            return false;
+        }
+    };
+
+    let solution = &solved.solution;
+    let verdicts = options.answers.as_ref().map(|answers| answers.check(solution));
+    let ok = !verdicts.iter().flatten().any(|v| matches!(v, Verdict::Fail { .. }));
+    match options.format {
+        Format::Text => {
+            match &options.answers {
+                Some(answers) => {
+                    answers.report(solution);
+                },
+                None => solution.print(),
+            }
+            if let Some(report) = report {
+                print!("{}", report);
+            }
+        },
+        Format::Json => {
+            let json = solution_json(puzzle, &name, solution, verdicts.as_ref(), report.as_ref());
+            println!("{}", json);
+        },
+    }
+    if let Some(argmatches) = &options.argmatches {
+        /* Standard output is all JSON, if that's the format. */
+        let exported = match options.format {
+            Format::Text => solved.export(argmatches, &mut io::stdout()),
+            Format::Json => solved.export(argmatches, &mut io::stderr()),
+        };
+        if let Err(e) = exported {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    }
+    ok
+}
+
+/* Runs a puzzle against each input in turn.  With more than one
+ * input, the text output gets a header naming each file.  Returns
+ * false if any of them failed.
+ */
+pub fn run_inputs(puzzle: &Puzzle, inputs: &[PathBuf], options: &RunOptions) -> bool {
+    let mut ok = true;
+    for input in inputs {
+        if inputs.len() > 1 && options.format == Format::Text {
+            println!("==> {} <==", input_name(input).display());
+        }
+        ok &= run_puzzle(puzzle, input, options);
+    }
+    ok
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::time::{Duration, Instant};
+
+use clap::{Arg, ArgMatches};
+
+use crate::ParseError;
+
+/* Common interface for every day's puzzle.  A solver is built by
+ * parsing the puzzle input, and then answers each part from that
+ * parsed state.
+ */
+pub trait Solver: Sized {
+    /* Day of the puzzle in the 2021 calendar. */
+    const DAY: u32;
+    /* Puzzle title, as shown on the website. */
+    const TITLE: &'static str;
+    /* Short name of the puzzle; inputs live in data/<NAME>.txt */
+    const NAME: &'static str;
+
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError>;
+
+    /* Command line options particular to this puzzle.  Values
+     * should be checked by clap, as configure() can't fail.
+     */
+    fn args<'help>() -> Vec<Arg<'help>> {
+        Vec::new()
+    }
+
+    /* Applies this puzzle's options once the input is parsed. */
+    fn configure(&mut self, _argmatches: &ArgMatches) {}
+
+    /* Parses and configures the puzzle from its options.  Puzzles
+     * with options that change what input they accept override this.
+     */
+    fn parse_with<R: BufRead>(input: R, argmatches: &ArgMatches) -> Result<Self, ParseError> {
+        let mut solver = Self::parse(input)?;
+        solver.configure(argmatches);
+        Ok(solver)
+    }
+
+    /* Whether the options ask export() for anything, so runners
+     * needn't keep the parsed puzzle around for it when they don't.
+     */
+    fn wants_export(_argmatches: &ArgMatches) -> bool {
+        false
+    }
+
+    /* Writes out anything extra asked for by this puzzle's options,
+     * such as images, once it's solved.  This isn't timed.  Anything
+     * printed goes to out, which the runner picks so it doesn't get
+     * mixed up with the answers (eg, standard error for JSON).
+     */
+    fn export(&self, _argmatches: &ArgMatches, _out: &mut dyn Write) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn part1(&self) -> Self::Answer1;
+    fn part2(&self) -> Self::Answer2;
+}
+
+/* Wall time spent in each phase of solving a puzzle. */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/* Answers for both parts of a puzzle, along with how
+ * long each phase took to run.
+ */
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub day: u32,
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+impl Solution {
+    pub fn print(&self) {
+        println!("Part 1: {}", self.part1);
+        println!("Part 2: {}", self.part2);
+    }
+}
+
+/* A parsed puzzle's export(), type-erased. */
+type Exporter = Box<dyn Fn(&ArgMatches, &mut dyn Write) -> Result<(), String>>;
+
+/* A puzzle's answers, along with the parsed puzzle if its options
+ * want anything exported from it, so exporting needn't parse the
+ * input all over again.
+ */
+pub struct Solved {
+    pub solution: Solution,
+    exporter: Option<Exporter>,
+}
+
+impl Solved {
+    /* The puzzle's export(), if its options asked for anything. */
+    pub fn export(&self, argmatches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+        match &self.exporter {
+            Some(export) => export(argmatches, out),
+            None => Ok(()),
+        }
+    }
+}
+
+/* Answers with nothing to export. */
+impl From<Solution> for Solved {
+    fn from(solution: Solution) -> Solved {
+        Solved { solution, exporter: None }
+    }
+}
+
+/* Parse the input and solve both parts, timing each phase. */
+pub fn solve<S: Solver, R: BufRead>(input: R) -> Result<Solution, ParseError> {
+    solve_with::<S, R>(input, None)
+}
+
+/* solve(), configuring the puzzle from its command line options
+ * first, if given.  Configuring is timed as part of parsing.
+ */
+pub fn solve_with<S: Solver, R: BufRead>(input: R, argmatches: Option<&ArgMatches>) -> Result<Solution, ParseError> {
+    solve_parsed::<S, R>(input, argmatches).map(|(solution, _)| solution)
+}
+
+/* solve_with(), handing back the parsed puzzle too. */
+fn solve_parsed<S: Solver, R: BufRead>(input: R, argmatches: Option<&ArgMatches>) -> Result<(Solution, S), ParseError> {
+    let start = Instant::now();
+    let solver = match argmatches {
+        Some(argmatches) => S::parse_with(input, argmatches)?,
+        None => S::parse(input)?,
+    };
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = solver.part1().to_string();
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = solver.part2().to_string();
+    let part2_time = start.elapsed();
+
+    let solution = Solution {
+        day: S::DAY,
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    };
+    Ok((solution, solver))
+}
+
+/* solve_with() over an in-memory input, for the Puzzle table.  The
+ * parsed puzzle is only kept if there's something to export.
+ */
+fn solve_bytes<S: Solver + 'static>(input: &[u8], argmatches: Option<&ArgMatches>) -> Result<Solved, ParseError> {
+    let (solution, solver) = solve_parsed::<S, _>(input, argmatches)?;
+    let exporter: Option<Exporter> = match argmatches {
+        Some(argmatches) if S::wants_export(argmatches) => Some(Box::new(move |argmatches, out| solver.export(argmatches, out))),
+        _ => None,
+    };
+    Ok(Solved { solution, exporter })
+}
+
+/* Type-erased handle on a Solver, so runners can keep a
+ * table of every puzzle.
+ */
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub day: u32,
+    pub title: &'static str,
+    pub name: &'static str,
+    pub args: fn() -> Vec<Arg<'static>>,
+    pub solve: fn(&[u8], Option<&ArgMatches>) -> Result<Solved, ParseError>,
+}
+
+impl Puzzle {
+    pub const fn of<S: Solver + 'static>() -> Puzzle {
+        Puzzle {
+            day: S::DAY,
+            title: S::TITLE,
+            name: S::NAME,
+            args: S::args,
+            solve: solve_bytes::<S>,
+        }
+    }
+}
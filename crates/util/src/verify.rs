@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use toml::Value;
+
+use crate::{ParseError, Solution};
+
+/* Expected answers for each day, loaded from a TOML file laid
+ * out like:
+ *
+ *   [day5]
+ *   part1 = 5294
+ *   part2 = 21698
+ *
+ * Answers may be integers or strings; either way they're compared
+ * against the printed form of the solver's answer.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    days: HashMap<u32, [Option<String>; 2]>,
+}
+
+/* Outcome of checking one part against its expected answer. */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Answers {
+    pub fn from_file(path: &Path) -> Result<Answers, ParseError> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| ParseError::new(format!("couldn't read answers: {}", e)).in_file(path))?;
+        Answers::from_toml(&text).map_err(|e| e.in_file(path))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Answers, ParseError> {
+        let table = match text.parse::<Value>() {
+            Ok(Value::Table(table)) => table,
+            Ok(_) => return Err(ParseError::new("expected a table of days")),
+            Err(e) => {
+                let mut err = ParseError::new(e.to_string());
+                if let Some((line, column)) = e.line_col() {
+                    let line_text = text.lines().nth(line).unwrap_or("");
+                    err = err.at_column(column + 1).on_line(line + 1, line_text);
+                }
+                return Err(err);
+            }
+        };
+
+        let mut answers = Answers::default();
+        for (key, parts) in table {
+            let day = match key.strip_prefix("day").and_then(|d| d.parse::<u32>().ok()) {
+                Some(day) => day,
+                None => return Err(ParseError::new(format!("expected [dayN] section, found [{}]", key))),
+            };
+            let parts = match parts {
+                Value::Table(parts) => parts,
+                _ => return Err(ParseError::new(format!("[{}] must be a table of part1/part2", key))),
+            };
+            let mut expected: [Option<String>; 2] = [None, None];
+            for (part, value) in parts {
+                let slot = match part.as_str() {
+                    "part1" => &mut expected[0],
+                    "part2" => &mut expected[1],
+                    _ => return Err(ParseError::new(format!("unknown key {}.{}", key, part))),
+                };
+                *slot = Some(match value {
+                    Value::String(s) => s,
+                    Value::Integer(i) => i.to_string(),
+                    v => return Err(ParseError::new(format!("{}.{} must be an integer or string, found {}", key, part, v))),
+                });
+            }
+            answers.days.insert(day, expected);
+        }
+        Ok(answers)
+    }
+
+    fn check_part(&self, day: u32, part: usize, answer: &str) -> Verdict {
+        match self.days.get(&day).and_then(|parts| parts[part].as_ref()) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected: expected.clone() },
+            None => Verdict::Missing,
+        }
+    }
+
+    /* Checks both parts of a solution against the expected answers. */
+    pub fn check(&self, solution: &Solution) -> [Verdict; 2] {
+        [
+            self.check_part(solution.day, 0, &solution.part1),
+            self.check_part(solution.day, 1, &solution.part2),
+        ]
+    }
+
+    /* Prints a PASS/FAIL line for each part, returning false on
+     * any mismatch.  Parts without an expected answer are skipped.
+     */
+    pub fn report(&self, solution: &Solution) -> bool {
+        let answers = [&solution.part1, &solution.part2];
+        let mut ok = true;
+        for (i, verdict) in self.check(solution).iter().enumerate() {
+            match verdict {
+                Verdict::Pass => println!("Part {}: {} {}", i + 1, verdict, answers[i]),
+                Verdict::Fail { expected } =>
+                    println!("Part {}: {} {} (expected {})", i + 1, verdict, answers[i], expected),
+                Verdict::Missing =>
+                    println!("Part {}: {} {} (no expected answer)", i + 1, verdict, answers[i]),
+            }
+            ok &= !matches!(verdict, Verdict::Fail { .. });
+        }
+        ok
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { .. } => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "SKIP"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Timings;
+
+    #[test]
+    fn test_answers_check() {
+        let answers = Answers::from_toml("[day5]\npart1 = 5\npart2 = \"12\"\n").unwrap();
+        let solution = Solution {
+            day: 5,
+            part1: "5".to_string(),
+            part2: "13".to_string(),
+            timings: Timings::default(),
+        };
+        assert_eq!(answers.check(&solution), [
+            Verdict::Pass,
+            Verdict::Fail { expected: "12".to_string() },
+        ]);
+
+        let solution = Solution { day: 6, ..solution };
+        assert_eq!(answers.check(&solution), [Verdict::Missing, Verdict::Missing]);
+    }
+
+    #[test]
+    fn test_answers_bad_section() {
+        let err = Answers::from_toml("[five]\npart1 = 5\n").unwrap_err();
+        assert!(err.reason().contains("[five]"));
+    }
+}
@@ -0,0 +1,66 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use vents::{Backend, Engine, VentSurvey};
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is 500 horizontal, vertical and diagonal lines
+ * on a 1000x1000 grid.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    (0..500 * scale)
+        .map(|_| {
+            let x1 = rng.range(0, 999);
+            let y1 = rng.range(0, 999);
+            let mut length = rng.range(1, 500);
+            let (dx, dy) = match rng.below(3) {
+                0 => (1, 0),
+                1 => (0, 1),
+                _ => (1, if rng.chance(1, 2) { 1 } else { -1 }),
+            };
+            let sign = if rng.chance(1, 2) { 1 } else { -1 };
+            /* Shorten the line so it stays on the grid. */
+            while !(0..1000).contains(&(x1 + sign * dx * length))
+                || !(0..1000).contains(&(y1 + sign * dy * length)) {
+                length /= 2;
+            }
+            format!("{},{} -> {},{}", x1, y1, x1 + sign * dx * length, y1 + sign * dy * length)
+        })
+        .collect()
+}
+
+fn parse(input: &[String]) -> VentSurvey {
+    VentSurvey::new(input).unwrap()
+}
+
+fn bench_vents(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vents");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+        let sparse = solver.clone().with_backend(Backend::Sparse);
+        group.bench_with_input(BenchmarkId::new("part2_sparse", scale), &sparse, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+        let sweep = solver.clone().with_engine(Engine::Sweep);
+        group.bench_with_input(BenchmarkId::new("part2_sweep", scale), &sweep, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_vents);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+/*
+ * Storage for the vent counts in a VentsMap: a grid over the
+ * bounding box of the lines, or a hash map of the cells they cover.
+ * Lines with depth get a box with layers, stacked in one grid.
+ */
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use util::Grid;
+
+use crate::{Raster, Vent, VentLine};
+
+/* Don't go dense if the box holds this many times the cells the
+ * lines cover; scanning that much empty grid costs more than
+ * hashing saves.
+ */
+const DENSE_VOLUME_PER_CELL: u64 = 16;
+
+/* Nor if it's bigger than this (128MB of counts). */
+const MAX_DENSE_VOLUME: u64 = 1 << 25;
+
+/* Where a VentsMap keeps its counts. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /* Dense if the lines fill enough of their bounding box. */
+    #[default]
+    Auto,
+    /* A grid over the bounding box of the lines. */
+    Dense,
+    /* A hash map of only the cells covered. */
+    Sparse,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(backend: &str) -> Result<Backend, String> {
+        match backend {
+            "auto" => Ok(Backend::Auto),
+            "dense" => Ok(Backend::Dense),
+            "sparse" => Ok(Backend::Sparse),
+            _ => Err(format!("unknown backend {:?}", backend)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Counts {
+    Sparse(HashMap<Vent, i32>),
+    /* Count of each cell in a box, whose top left (and shallowest)
+     * corner is origin.  Each z layer of the box is height rows of
+     * the grid, below the layer above it.
+     */
+    Dense { origin: Vent, height: usize, grid: Grid<i32> },
+}
+
+/* Smallest box holding every line, as its top left and bottom right
+ * corners (shallowest and deepest, too), or None if there are no
+ * lines.  Lines never leave the box
+ * around their ends, whichever raster they're drawn with.
+ */
+pub(crate) fn bounding_box<'a, I>(lines: I) -> Option<(Vent, Vent)>
+where
+    I: IntoIterator<Item = &'a VentLine>,
+{
+    lines.into_iter().fold(None, |bbox, line| {
+        let (min, max) = bbox.unwrap_or((line.start(), line.start()));
+        let (start, end) = (line.start().coords(), line.end().coords());
+        let (mut lo, mut hi) = (min.coords(), max.coords());
+        for axis in 0..3 {
+            lo[axis] = lo[axis].min(start[axis]).min(end[axis]);
+            hi[axis] = hi[axis].max(start[axis]).max(end[axis]);
+        }
+        Some((Vent::from_coords(lo), Vent::from_coords(hi)))
+    })
+}
+
+/* Whether the lines' bounding box is too big for a dense grid,
+ * which is when drawing them starts to take a while.
+ */
+pub(crate) fn is_large(lines: &[VentLine]) -> bool {
+    let (min, max) = match bounding_box(lines) {
+        Some(bbox) => bbox,
+        None => return false,
+    };
+    let size = |lo: i32, hi: i32| (hi as i64 - lo as i64) as u64 + 1;
+    let volume = size(min.x, max.x).saturating_mul(size(min.y, max.y)).saturating_mul(size(min.z, max.z));
+    volume > MAX_DENSE_VOLUME
+}
+
+impl Counts {
+    /* Empty counts for the given lines, stored however the backend
+     * says, or for Auto, however looks cheaper.
+     */
+    pub(crate) fn for_lines(lines: &[VentLine], raster: Raster, backend: Backend) -> Counts {
+        let (min, max) = match bounding_box(lines) {
+            Some(bbox) => bbox,
+            None => return Counts::Sparse(HashMap::new()),
+        };
+        let size = |lo: i32, hi: i32| (hi as i64 - lo as i64) as usize + 1;
+        let (width, height, depth) = (size(min.x, max.x), size(min.y, max.y), size(min.z, max.z));
+        let dense = match backend {
+            Backend::Dense => true,
+            Backend::Sparse => false,
+            Backend::Auto => {
+                let volume = width as u64 * height as u64 * depth as u64;
+                let cells: u64 = lines.iter().map(|line| line.point_count(raster) as u64).sum();
+                volume <= MAX_DENSE_VOLUME && volume <= cells * DENSE_VOLUME_PER_CELL
+            },
+        };
+        if dense {
+            Counts::Dense { origin: min, height, grid: Grid::new(width, height * depth, 0) }
+        } else {
+            Counts::Sparse(HashMap::with_capacity(lines.len()))
+        }
+    }
+
+    pub(crate) fn backend(&self) -> Backend {
+        match self {
+            Counts::Sparse(_) => Backend::Sparse,
+            Counts::Dense { .. } => Backend::Dense,
+        }
+    }
+
+    /* Dense counts must be sized to hold the vent. */
+    pub(crate) fn increment(&mut self, vent: &Vent) {
+        match self {
+            Counts::Sparse(map) => *map.entry(*vent).or_insert(0) += 1,
+            Counts::Dense { origin, height, grid } => {
+                let offset = |coord: i32, origin: i32| (coord as i64 - origin as i64) as usize;
+                let layer = offset(vent.z, origin.z) * *height;
+                grid[(offset(vent.x, origin.x), layer + offset(vent.y, origin.y))] += 1
+            },
+        }
+    }
+
+    /* Every vent with a count, in no particular order. */
+    pub(crate) fn iter(&self) -> Box<dyn Iterator<Item = (Vent, i32)> + '_> {
+        match self {
+            Counts::Sparse(map) => Box::new(map.iter().map(|(vent, count)| (*vent, *count))),
+            Counts::Dense { origin, height, grid } => Box::new(grid
+                .positions()
+                .map(move |(x, row)| {
+                    let (y, z) = (row % height, row / height);
+                    let vent = Vent::new(origin.x + x as i32, origin.y + y as i32).with_z(origin.z + z as i32);
+                    (vent, grid[(x, row)])
+                })
+                .filter(|&(_, count)| count > 0)),
+        }
+    }
+
+    pub(crate) fn count_at_least(&self, threshold: i32) -> usize {
+        match self {
+            Counts::Sparse(map) => map.values().filter(|&&count| count >= threshold).count(),
+            Counts::Dense { grid, .. } => grid.iter().filter(|&&count| count > 0 && count >= threshold).count(),
+        }
+    }
+}
@@ -0,0 +1,700 @@
+/*
+ * https://adventofcode.com/2021/day/5
+ */
+
+use std::io::{BufRead, Write};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use clap::{Arg, ArgMatches};
+
+use util::{Grid, ParseError, Solver};
+
+mod backend;
+mod raster;
+mod render;
+mod report;
+mod sweep;
+
+use backend::Counts;
+
+pub use backend::Backend;
+pub use raster::{Points, Raster};
+pub use render::Region;
+pub use report::{Hotspot, OverlapReport};
+pub use sweep::Engine;
+
+const OVERLAPPING_VENT_THRESHOLD: i32 = 2;
+
+/* A cell of the map.  Surveys without depth are all at z = 0. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Vent {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Vent {
+    pub fn new(x: i32, y: i32) -> Vent {
+        Vent { x, y, z: 0 }
+    }
+
+    pub fn with_z(mut self, z: i32) -> Vent {
+        self.z = z;
+        self
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+
+    pub(crate) fn coords(&self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub(crate) fn from_coords(coords: [i32; 3]) -> Vent {
+        Vent { x: coords[0], y: coords[1], z: coords[2] }
+    }
+}
+
+/* Line from point 1 -> point 2, at z = 0 unless it's given
+ * z coordinates.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VentLine {
+    x1: i32,
+    y1: i32,
+    z1: i32,
+    x2: i32,
+    y2: i32,
+    z2: i32,
+}
+
+impl VentLine {
+    pub fn from_string(text: &str) -> Result<VentLine, ParseError> {
+        // 0,9 -> 5,9 or 0,9,2 -> 5,9,2
+        let v: Vec<&str> = text.split(" -> ").collect();
+        if v.len() != 2 {
+            return Err(ParseError::new("expected a line like \"0,9 -> 5,9\"").at_column(1));
+        }
+        let origin = VentLine::parse_point(text, v[0])?;
+        let ending = VentLine::parse_point(text, v[1])?;
+        if ending.len() != origin.len() {
+            return Err(ParseError::new(format!("expected {} coordinates, as at the start: {:?}", origin.len(), v[1]))
+                .in_field(text, v[1]));
+        }
+        let z = |point: &[i32]| point.get(2).copied().unwrap_or(0);
+        Ok(VentLine {
+            x1: origin[0],
+            y1: origin[1],
+            z1: z(&origin),
+            x2: ending[0],
+            y2: ending[1],
+            z2: z(&ending),
+        })
+    }
+
+    fn parse_point(text: &str, point: &str) -> Result<Vec<i32>, ParseError> {
+        let coords: Vec<&str> = point.split(',').collect();
+        if coords.len() != 2 && coords.len() != 3 {
+            return Err(ParseError::new(format!("expected an x,y or x,y,z coordinate: {:?}", point))
+                .in_field(text, point));
+        }
+        coords.iter().map(|coord| util::parse_field(text, coord)).collect()
+    }
+
+    pub fn start(&self) -> Vent {
+        Vent { x: self.x1, y: self.y1, z: self.z1 }
+    }
+
+    pub fn end(&self) -> Vent {
+        Vent { x: self.x2, y: self.y2, z: self.z2 }
+    }
+
+    /* Whether the line leaves the z = 0 plane. */
+    pub fn has_depth(&self) -> bool {
+        self.z1 != 0 || self.z2 != 0
+    }
+}
+
+/* Every vent line from the survey, as read from the puzzle input. */
+#[derive(Clone, Debug)]
+pub struct VentSurvey {
+    lines: Vec<VentLine>,
+    /* How lines at odd angles are drawn onto a VentsMap. */
+    raster: Raster,
+    /* How a VentsMap stores its counts. */
+    backend: Backend,
+    /* How the overlaps are counted. */
+    engine: Engine,
+}
+
+impl VentSurvey {
+    pub fn from_reader<R: BufRead>(input: R) -> Result<VentSurvey, ParseError> {
+        let ventline_strings = util::read_lines(input)?;
+        VentSurvey::new(&ventline_strings)
+    }
+
+    pub fn new(ventline_strings: &[String]) -> Result<VentSurvey, ParseError> {
+        // Convert ventline strings into ventline objects
+        let lines = util::parse_lines(ventline_strings, VentLine::from_string)?;
+        Ok(VentSurvey {
+            lines,
+            raster: Raster::Bresenham,
+            backend: Backend::Auto,
+            engine: Engine::Raster,
+        })
+    }
+
+    pub fn with_raster(mut self, raster: Raster) -> VentSurvey {
+        self.raster = raster;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: Backend) -> VentSurvey {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_engine(mut self, engine: Engine) -> VentSurvey {
+        self.engine = engine;
+        self
+    }
+
+    /* Whether any line leaves the z = 0 plane. */
+    pub fn has_depth(&self) -> bool {
+        self.lines.iter().any(VentLine::has_depth)
+    }
+
+    /* Number of cells with overlapping vents, counted by the engine.
+     * The sweep only works in the plane, so lines with depth are
+     * drawn instead.
+     */
+    pub fn overlap_count(&self, use_diagonals: bool) -> i64 {
+        match self.engine {
+            Engine::Sweep if !self.has_depth() => self.sweep_overlap_count(use_diagonals),
+            _ => VentsMap::from_survey(self, use_diagonals)
+                .overlapping_vent_count(OVERLAPPING_VENT_THRESHOLD) as i64,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VentsMap {
+    counts: Counts,
+    /* The lines drawn, and how, for finding which cover a cell. */
+    lines: Vec<VentLine>,
+    raster: Raster,
+}
+
+impl VentsMap {
+    pub fn from_reader<R: BufRead>(input: R, use_diagonals: bool) -> Result<VentsMap, ParseError> {
+        Ok(VentsMap::from_survey(&VentSurvey::from_reader(input)?, use_diagonals))
+    }
+
+    /* Lines that aren't horizontal or vertical are only drawn when
+     * use_diagonals is set (eg, for part 2), using the survey's raster
+     * policy.
+     */
+    pub fn from_survey(survey: &VentSurvey, use_diagonals: bool) -> VentsMap {
+        let lines: Vec<VentLine> = survey.lines
+            .iter()
+            .filter(|ventline| use_diagonals || ventline.is_axis_aligned())
+            .copied()
+            .collect();
+        let mut counts = Counts::for_lines(&lines, survey.raster, survey.backend);
+        for ventline in &lines {
+            VentsMap::apply_line(&mut counts, ventline, survey.raster);
+        }
+        VentsMap {
+            counts,
+            lines,
+            raster: survey.raster,
+        }
+    }
+
+    /* Which backend the counts ended up in: Dense or Sparse. */
+    pub fn backend(&self) -> Backend {
+        self.counts.backend()
+    }
+
+    /* Apply a line to the ventmap.  If an existing entry already exists for
+     * the Vent coordinate, it'll be incremented.
+     */
+    fn apply_line(counts: &mut Counts, ventline: &VentLine, raster: Raster) {
+        for vent in ventline.points(raster) {
+            counts.increment(&vent);
+        }
+    }
+
+    /* Smallest region holding every vent, negative coordinates
+     * and all, seen from above, or None if there are no vents.
+     */
+    pub fn bounds(&self) -> Option<Region> {
+        backend::bounding_box(&self.lines).map(|(min, max)| Region::new(min, max))
+    }
+
+    pub fn overlapping_vent_count(&self, threshold: i32) -> i32 {
+        self.counts.count_at_least(threshold) as i32
+    }
+
+    /* Vent counts over bounds(), as a grid whose top left cell is
+     * the top left corner of bounds(), wherever that is.  Counts at
+     * different depths add up in the cell above them.
+     */
+    pub fn to_grid(&self) -> Grid<i32> {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Grid::new(0, 0, 0),
+        };
+        let origin = bounds.min();
+        let mut grid = Grid::new(bounds.width(), bounds.height(), 0);
+        for (vent, count) in self.counts.iter() {
+            grid[((vent.x - origin.x) as usize, (vent.y - origin.y) as usize)] += count;
+        }
+        grid
+    }
+}
+
+impl fmt::Display for VentsMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let grid = self.to_grid().map(|&v| if v == 0 { ".".to_string() } else { v.to_string() });
+        writeln!(f, "{}", grid)
+    }
+}
+
+impl fmt::Display for Vent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.z != 0 {
+            write!(f, "{},{},{}", self.x, self.y, self.z)
+        } else {
+            write!(f, "{},{}", self.x, self.y)
+        }
+    }
+}
+
+impl fmt::Display for VentLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.has_depth() {
+            write!(f, "{},{},{} -> {},{},{}", self.x1, self.y1, self.z1, self.x2, self.y2, self.z2)
+        } else {
+            write!(f, "{},{} -> {},{}", self.x1, self.y1, self.x2, self.y2)
+        }
+    }
+}
+
+impl Solver for VentSurvey {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Hydrothermal Venture";
+    const NAME: &'static str = "vents";
+
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        VentSurvey::from_reader(input)
+    }
+
+    fn args<'help>() -> Vec<Arg<'help>> {
+        vec![
+            Arg::new("raster")
+                .about("How lines that aren't horizontal, vertical or 45 degrees cover cells: bresenham, or only the lattice points they pass exactly through")
+                .long("raster")
+                .takes_value(true)
+                .possible_values(["bresenham", "lattice"])
+                .default_value("bresenham"),
+            Arg::new("backend")
+                .about("Count vents in a grid over the lines' bounding box, a hash map of the cells covered, or whichever looks cheaper")
+                .long("backend")
+                .takes_value(true)
+                .possible_values(["auto", "dense", "sparse"])
+                .default_value("auto"),
+            Arg::new("engine")
+                .about("Count overlaps by drawing the lines, or by sweeping over them, drawing only lines at odd angles under bresenham (lines with depth are always drawn)")
+                .long("engine")
+                .takes_value(true)
+                .possible_values(["raster", "sweep"])
+                .default_value("raster"),
+            Arg::new("report")
+                .about("Print how many cells part 2's lines cover how many times, and which lines cover the worst overlaps (to stderr with --format json)")
+                .long("report"),
+            Arg::new("svg")
+                .about("Draw the lines to an SVG file")
+                .long("svg")
+                .value_name("FILE")
+                .takes_value(true),
+            Arg::new("heatmap")
+                .about("Write a .png or .ppm image of how many vents are in each cell")
+                .long("heatmap")
+                .value_name("IMAGE")
+                .takes_value(true),
+            Arg::new("heatmap-scale")
+                .about("Draw each cell as an N x N square in --heatmap")
+                .long("heatmap-scale")
+                .value_name("N")
+                .takes_value(true)
+                .validator(|n| n.parse::<usize>())
+                .default_value("1"),
+            Arg::new("crop")
+                .about("Only draw the cells from x1,y1 to x2,y2 in --svg and --heatmap")
+                .long("crop")
+                .value_name("X1,Y1,X2,Y2")
+                .takes_value(true)
+                .allow_hyphen_values(true)
+                .validator(|region| region.parse::<Region>()),
+            Arg::new("no-diagonals")
+                .about("Leave out lines that aren't horizontal or vertical from --svg and --heatmap, as in part 1")
+                .long("no-diagonals"),
+        ]
+    }
+
+    fn configure(&mut self, argmatches: &ArgMatches) {
+        if let Some(raster) = argmatches.value_of("raster") {
+            self.raster = raster.parse().unwrap_or_default();
+        }
+        if let Some(backend) = argmatches.value_of("backend") {
+            self.backend = backend.parse().unwrap_or_default();
+        }
+        if let Some(engine) = argmatches.value_of("engine") {
+            self.engine = engine.parse().unwrap_or_default();
+        }
+        if self.engine == Engine::Sweep && self.has_depth() && backend::is_large(&self.lines) {
+            eprintln!("warning: the sweep can't count lines with depth, so they're drawn, over a big volume");
+        }
+    }
+
+    fn wants_export(argmatches: &ArgMatches) -> bool {
+        ["report", "svg", "heatmap"].iter().any(|&arg| argmatches.is_present(arg))
+    }
+
+    fn export(&self, argmatches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+        if argmatches.is_present("report") {
+            write!(out, "{}", VentsMap::from_survey(self, true).report())
+                .map_err(|e| format!("couldn't print report: {}", e))?;
+        }
+        let (svg, heatmap) = (argmatches.value_of("svg"), argmatches.value_of("heatmap"));
+        if svg.is_none() && heatmap.is_none() {
+            return Ok(());
+        }
+        let vents_map = VentsMap::from_survey(self, !argmatches.is_present("no-diagonals"));
+        let crop = argmatches.value_of("crop").map(str::parse::<Region>).transpose()?;
+        if let Some(path) = svg {
+            fs::write(path, vents_map.to_svg(crop))
+                .map_err(|e| format!("couldn't write {}: {}", path, e))?;
+        }
+        if let Some(path) = heatmap {
+            let scale = argmatches.value_of("heatmap-scale").map_or(1, |n| n.parse().unwrap_or(1));
+            let image = util::image::upscale(&vents_map.heatmap(crop), scale);
+            util::image::save_image(&image, Path::new(path))
+                .map_err(|e| format!("couldn't write {}: {}", path, e))?;
+        }
+        Ok(())
+    }
+
+    fn part1(&self) -> i64 {
+        self.overlap_count(false)
+    }
+
+    fn part2(&self) -> i64 {
+        self.overlap_count(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{PathBuf};
+    use util::gen::Rng;
+    
+    #[test]
+    fn test_ventline_from_string() {
+        let text = "0,2 -> 9,4".to_string();
+        let ventline = VentLine::from_string(&text).unwrap();
+        assert_eq!(ventline.x1, 0);
+        assert_eq!(ventline.y1, 2);
+        assert_eq!(ventline.x2, 9);
+        assert_eq!(ventline.y2, 4);
+    }
+
+    #[test]
+    fn test_ventline_from_string_bad_coordinate() {
+        let err = VentLine::from_string("0,2 -> 9,four").unwrap_err();
+        assert_eq!(err.column(), 10);
+        let err = VentLine::from_string("0,2 => 9,4").unwrap_err();
+        assert_eq!(err.column(), 1);
+    }
+    
+    #[test]
+    fn test_vents_part1() {
+        const OVERLAPPING_VENT_THRESHOLD: i32 = 2;
+        const OVERLAPPING_VENT_COUNT: i32 = 5;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let vents_map = VentsMap::from_reader(util::open(&input).unwrap(), false).unwrap();
+        println!("{}", vents_map);
+        assert_eq!(vents_map.overlapping_vent_count(OVERLAPPING_VENT_THRESHOLD), OVERLAPPING_VENT_COUNT);
+    }
+
+    #[test]
+    fn test_vents_part2() {
+        const OVERLAPPING_VENT_THRESHOLD: i32 = 2;
+        const OVERLAPPING_VENT_COUNT: i32 = 12;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let vents_map = VentsMap::from_reader(util::open(&input).unwrap(), true).unwrap();
+        println!("{}", vents_map);
+        assert_eq!(vents_map.overlapping_vent_count(OVERLAPPING_VENT_THRESHOLD), OVERLAPPING_VENT_COUNT);
+    }
+
+    #[test]
+    fn test_ventline_points() {
+        let ventline = VentLine::from_string("0,0 -> 4,2").unwrap();
+        let points: Vec<(i32, i32)> = ventline.points(Raster::Bresenham).map(|v| (v.x, v.y)).collect();
+        assert_eq!(points, [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+        let points: Vec<(i32, i32)> = ventline.points(Raster::Lattice).map(|v| (v.x, v.y)).collect();
+        assert_eq!(points, [(0, 0), (2, 1), (4, 2)]);
+
+        let ventline = VentLine::from_string("5,5 -> 2,8").unwrap();
+        let bresenham: Vec<Vent> = ventline.points(Raster::Bresenham).collect();
+        assert_eq!(bresenham, ventline.points(Raster::Lattice).collect::<Vec<Vent>>());
+        assert_eq!(bresenham.len(), 4);
+        assert_eq!(VentLine::from_string("3,3 -> 3,3").unwrap().points(Raster::Lattice).count(), 1);
+
+        let lines: Vec<String> = ["0,0 -> 4,2", "1,0 -> 1,4", "3,0 -> 3,4"].iter().map(|l| l.to_string()).collect();
+        let survey = VentSurvey::new(&lines).unwrap();
+        assert_eq!(VentsMap::from_survey(&survey, false).overlapping_vent_count(2), 0);
+        assert_eq!(VentsMap::from_survey(&survey, true).overlapping_vent_count(2), 2);
+        let survey = survey.with_raster(Raster::Lattice);
+        assert_eq!(VentsMap::from_survey(&survey, true).overlapping_vent_count(2), 0);
+    }
+
+    #[test]
+    fn test_vents_backends() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let survey = VentSurvey::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(VentsMap::from_survey(&survey, true).backend(), Backend::Dense);
+        let maps: Vec<VentsMap> = [Backend::Dense, Backend::Sparse]
+            .iter()
+            .map(|&backend| VentsMap::from_survey(&survey.clone().with_backend(backend), true))
+            .collect();
+        assert_eq!((maps[0].backend(), maps[1].backend()), (Backend::Dense, Backend::Sparse));
+        assert_eq!(maps[0].overlapping_vent_count(2), 12);
+        assert_eq!(maps[1].overlapping_vent_count(2), 12);
+        assert_eq!(maps[0].to_string(), maps[1].to_string());
+
+        let lines = vec!["0,0 -> 0,5".to_string(), "1000,1000 -> 1000,1001".to_string()];
+        let survey = VentSurvey::new(&lines).unwrap();
+        assert_eq!(VentsMap::from_survey(&survey, true).backend(), Backend::Sparse);
+    }
+
+    #[test]
+    fn test_vents_sweep() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let survey = VentSurvey::from_reader(util::open(&input).unwrap()).unwrap();
+        assert_eq!(survey.sweep_overlap_count(false), 5);
+        assert_eq!(survey.sweep_overlap_count(true), 12);
+
+        let lines: Vec<String> = ["0,2 -> 4,2", "5,2 -> 1,2", "2,0 -> 2,4", "2,1 -> 2,5"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(VentSurvey::new(&lines).unwrap().sweep_overlap_count(false), 7);
+
+        let mut rng = Rng::new(5);
+        for _ in 0..200 {
+            let lines: Vec<String> = (0..rng.range(1, 24))
+                .map(|_| {
+                    let mut coord = || rng.range(0, 10);
+                    format!("{},{} -> {},{}", coord(), coord(), coord(), coord())
+                })
+                .collect();
+            let survey = VentSurvey::new(&lines).unwrap().with_raster(Raster::Lattice);
+            for &use_diagonals in &[false, true] {
+                let drawn = VentsMap::from_survey(&survey, use_diagonals).overlapping_vent_count(2) as i64;
+                assert_eq!(survey.sweep_overlap_count(use_diagonals), drawn, "{:?}", lines);
+            }
+        }
+
+        let lines: Vec<String> = ["0,0 -> 3000000,0", "5000000,0 -> 1000000,0", "2000000,-7 -> 2000000,7", "4,4 -> 4,4", "4,4 -> 8,8"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let survey = VentSurvey::new(&lines).unwrap().with_engine(Engine::Sweep);
+        assert_eq!(survey.overlap_count(false), 2000001);
+        assert_eq!(survey.overlap_count(true), 2000002);
+
+        let lines: Vec<String> = ["0,0 -> 4,2", "1,0 -> 1,4", "3,0 -> 3,4"].iter().map(|l| l.to_string()).collect();
+        let survey = VentSurvey::new(&lines).unwrap();
+        for &raster in &[Raster::Bresenham, Raster::Lattice] {
+            let drawn = survey.clone().with_raster(raster);
+            let swept = drawn.clone().with_engine(Engine::Sweep);
+            assert_eq!(swept.overlap_count(true), drawn.overlap_count(true), "{:?}", raster);
+        }
+        assert_eq!(survey.clone().with_engine(Engine::Sweep).overlap_count(true), 2);
+
+        /* Odd angles under bresenham are drawn and merged with the sweep. */
+        let mut rng = Rng::new(21);
+        for _ in 0..200 {
+            let lines: Vec<String> = (0..rng.range(1, 24))
+                .map(|_| {
+                    let mut coord = || rng.range(-6, 10);
+                    format!("{},{} -> {},{}", coord(), coord(), coord(), coord())
+                })
+                .collect();
+            let drawn = VentSurvey::new(&lines).unwrap();
+            let swept = drawn.clone().with_engine(Engine::Sweep);
+            for &use_diagonals in &[false, true] {
+                assert_eq!(swept.overlap_count(use_diagonals), drawn.overlap_count(use_diagonals), "{:?}", lines);
+            }
+        }
+
+        let lines: Vec<String> = ["0,0 -> 4000,0", "-1000,-1 -> 1000,1", "1,-2 -> 3,2", "2,-3 -> 2,3000"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let survey = VentSurvey::new(&lines).unwrap();
+        assert_eq!(survey.clone().with_engine(Engine::Sweep).overlap_count(true), survey.overlap_count(true));
+    }
+
+    #[test]
+    fn test_vents_report() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let vents_map = VentsMap::from_reader(util::open(&input).unwrap(), true).unwrap();
+        let report = vents_map.report();
+        assert_eq!(report.histogram, [0, 27, 10, 2]);
+        assert_eq!(report.at_least(2), vents_map.overlapping_vent_count(2) as usize);
+        assert_eq!(report.max_coverage, 3);
+        let hotspots: Vec<(i32, i32)> = report.hotspots.iter().map(|h| (h.vent.x(), h.vent.y())).collect();
+        assert_eq!(hotspots, [(4, 4), (6, 4)]);
+        let lines: Vec<String> = report.hotspots[0].lines.iter().map(VentLine::to_string).collect();
+        assert_eq!(lines, ["8,0 -> 0,8", "9,4 -> 3,4", "0,0 -> 8,8"]);
+    }
+
+    #[test]
+    fn test_vents_render() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let vents_map = VentsMap::from_reader(util::open(&input).unwrap(), true).unwrap();
+        let image = vents_map.heatmap(None);
+        assert_eq!((image.width(), image.height()), (10, 10));
+        assert_eq!(image[(1, 0)], [0, 0, 0]);
+        assert_eq!(image[(4, 4)], util::image::colormap(1.0));
+        assert_eq!(image[(0, 0)], util::image::colormap(0.0));
+
+        let crop: Region = "4,3,6,5".parse().unwrap();
+        let image = vents_map.heatmap(Some(crop));
+        assert_eq!((image.width(), image.height()), (3, 3));
+        assert_eq!(image[(0, 1)], image[(2, 1)]);
+
+        assert_eq!(vents_map.to_svg(None).matches("<line ").count(), 10);
+        let svg = VentsMap::from_reader(util::open(&input).unwrap(), false).unwrap().to_svg(Some(crop));
+        assert_eq!(svg.matches("<line ").count(), 1);
+        assert!(svg.contains("viewBox=\"3.5 2.5 3 3\""));
+        assert!("1,2,3".parse::<Region>().is_err());
+    }
+
+    #[test]
+    fn test_vents_negative() {
+        let lines: Vec<String> = ["-3,-2 -> 1,-2", "-1,-4 -> -1,0", "-3,0 -> 1,-4"].iter().map(|l| l.to_string()).collect();
+        let survey = VentSurvey::new(&lines).unwrap();
+        let vents_map = VentsMap::from_survey(&survey, false);
+        assert_eq!(vents_map.bounds(), Some("-3,-4,1,0".parse().unwrap()));
+        assert_eq!(vents_map.backend(), Backend::Dense);
+        assert_eq!(vents_map.to_string(), "..1..\n..1..\n11211\n..1..\n..1..\n\n");
+        let sparse = VentsMap::from_survey(&survey.clone().with_backend(Backend::Sparse), false);
+        assert_eq!(sparse.to_string(), vents_map.to_string());
+
+        let vents_map = VentsMap::from_survey(&survey, true);
+        assert_eq!(vents_map.overlapping_vent_count(2), 1);
+        assert_eq!(survey.sweep_overlap_count(true), 1);
+        let image = vents_map.heatmap(None);
+        assert_eq!((image.width(), image.height()), (5, 5));
+        assert_eq!(image[(2, 2)], util::image::colormap(1.0));
+        assert!(vents_map.to_svg(None).contains("viewBox=\"-3.5 -4.5 5 5\""));
+    }
+
+    #[test]
+    fn test_vents_depth() {
+        let ventline = VentLine::from_string("1,2,3 -> 4,5,-6").unwrap();
+        assert_eq!((ventline.x1, ventline.y1, ventline.z1), (1, 2, 3));
+        assert_eq!((ventline.x2, ventline.y2, ventline.z2), (4, 5, -6));
+        assert_eq!(ventline.to_string(), "1,2,3 -> 4,5,-6");
+        assert_eq!(VentLine::from_string("1,2,0 -> 4,5,0").unwrap().to_string(), "1,2 -> 4,5");
+        let err = VentLine::from_string("1,2,3 -> 4,5").unwrap_err();
+        assert_eq!(err.column(), 10);
+
+        let ventline = VentLine::from_string("0,0,0 -> 4,2,1").unwrap();
+        let points: Vec<Vent> = ventline.points(Raster::Bresenham).collect();
+        let plane = |line: &str| VentLine::from_string(line).unwrap().points(Raster::Bresenham).collect::<Vec<Vent>>();
+        assert_eq!(points.iter().map(|v| Vent::new(v.x, v.y)).collect::<Vec<Vent>>(), plane("0,0 -> 4,2"));
+        assert_eq!(points.iter().map(|v| Vent::new(v.x, v.z)).collect::<Vec<Vent>>(), plane("0,0 -> 4,1"));
+        assert_eq!(VentLine::from_string("0,0,0 -> 4,2,2").unwrap().points(Raster::Lattice).count(), 3);
+        assert_eq!(VentLine::from_string("3,3,3 -> 0,0,0").unwrap().points(Raster::Lattice).count(), 4);
+
+        let lines: Vec<String> = ["0,0,0 -> 4,0,0", "2,0,-2 -> 2,0,2", "0,0,1 -> 4,0,1", "0,4,4 -> 4,0,0"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let survey = VentSurvey::new(&lines).unwrap();
+        assert!(survey.has_depth());
+        assert_eq!(survey.part1(), 2);
+        assert_eq!(survey.part2(), 3);
+        assert_eq!(survey.clone().with_engine(Engine::Sweep).part2(), 3);
+        let maps: Vec<VentsMap> = [Backend::Dense, Backend::Sparse]
+            .iter()
+            .map(|&backend| VentsMap::from_survey(&survey.clone().with_backend(backend), true))
+            .collect();
+        assert_eq!(maps[0].to_string(), maps[1].to_string());
+        let hotspots: Vec<String> = maps[0].report().hotspots.iter().map(|h| h.vent.to_string()).collect();
+        assert_eq!(hotspots, ["2,0", "4,0", "2,0,1"]);
+    }
+
+    #[test]
+    fn test_vents_extremes() {
+        let corners = VentLine::from_string("-2147483648,-2147483648 -> 2147483647,2147483647").unwrap();
+        assert!(corners.is_octilinear());
+        assert_eq!(corners.point_count(Raster::Lattice), 1 << 32);
+        let points: Vec<Vent> = corners.points(Raster::Bresenham).take(2).collect();
+        assert_eq!(points, [Vent::new(i32::MIN, i32::MIN), Vent::new(i32::MIN + 1, i32::MIN + 1)]);
+
+        let steep = VentLine::from_string("2147483647,0 -> -2147483648,1").unwrap();
+        let points: Vec<Vent> = steep.points(Raster::Lattice).collect();
+        assert_eq!(points, [Vent::new(i32::MAX, 0), Vent::new(i32::MIN, 1)]);
+        assert_eq!(steep.point_count(Raster::Bresenham), 1 << 32);
+        let points: Vec<Vent> = steep.points(Raster::Bresenham).take(2).collect();
+        assert_eq!(points, [Vent::new(i32::MAX, 0), Vent::new(i32::MAX - 1, 0)]);
+
+        let lines: Vec<String> = ["-2147483648,0 -> 2147483647,0", "0,-5 -> 0,5"].iter().map(|l| l.to_string()).collect();
+        let survey = VentSurvey::new(&lines).unwrap().with_engine(Engine::Sweep);
+        assert_eq!(survey.overlap_count(false), 1);
+    }
+
+    #[test]
+    fn test_vents_display() {
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("vents_test.txt");
+        let vents_map = VentsMap::from_reader(util::open(&input).unwrap(), false).unwrap();
+        assert_eq!(vents_map.to_string(), concat!(
+            ".......1..\n",
+            "..1....1..\n",
+            "..1....1..\n",
+            ".......1..\n",
+            ".112111211\n",
+            "..........\n",
+            "..........\n",
+            "..........\n",
+            "..........\n",
+            "222111....\n",
+            "\n",
+        ));
+    }
+}
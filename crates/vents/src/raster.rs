@@ -0,0 +1,176 @@
+/*
+ * Which grid cells a vent line covers, at any angle, in two
+ * dimensions or three.
+ */
+
+use std::str::FromStr;
+
+use crate::{Vent, VentLine};
+
+/* How to rasterize lines that aren't along an axis or at 45
+ * degrees.  Those come out the same either way.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Raster {
+    /* Every cell the line passes closest to, with no gaps. */
+    #[default]
+    Bresenham,
+    /* Only the cells the line passes exactly through. */
+    Lattice,
+}
+
+impl FromStr for Raster {
+    type Err = String;
+
+    fn from_str(raster: &str) -> Result<Raster, String> {
+        match raster {
+            "bresenham" => Ok(Raster::Bresenham),
+            "lattice" => Ok(Raster::Lattice),
+            _ => Err(format!("unknown raster policy {:?}", raster)),
+        }
+    }
+}
+
+/* Iterator over the cells of a line, from point 1 to point 2. */
+#[derive(Clone, Debug)]
+pub struct Points {
+    at: Option<Vent>,
+    end: Vent,
+    walk: Walk,
+}
+
+#[derive(Clone, Debug)]
+enum Walk {
+    /* Fixed steps between the lattice points.  A step can be wider
+     * than an i32 when the ends are, but never leaves the line.
+     */
+    Lattice { step: [i64; 3] },
+    /* The axis changing most steps every time; the others step as
+     * the error terms of their planes with it decide.
+     */
+    Bresenham { drive: usize, signs: [i32; 3], planes: [Plane; 2] },
+}
+
+/* Bresenham's line in the plane of the driving axis and one other.
+ * The lower numbered axis plays x and the other y, as in the 2D
+ * algorithm, so lines in a plane come out as they would in 2D.
+ */
+#[derive(Clone, Copy, Debug)]
+struct Plane {
+    axis: usize,
+    axis_is_x: bool,
+    dx: i64,
+    dy: i64,
+    err: i64,
+}
+
+impl Iterator for Points {
+    type Item = Vent;
+
+    fn next(&mut self) -> Option<Vent> {
+        let vent = self.at?;
+        self.at = if vent == self.end {
+            None
+        } else {
+            Some(self.walk.step(vent))
+        };
+        Some(vent)
+    }
+}
+
+impl Walk {
+    fn step(&mut self, vent: Vent) -> Vent {
+        let mut coords = vent.coords();
+        match self {
+            Walk::Lattice { step } => {
+                for (coord, step) in coords.iter_mut().zip(step.iter()) {
+                    *coord = (*coord as i64 + step) as i32;
+                }
+            },
+            Walk::Bresenham { drive, signs, planes } => {
+                for plane in planes.iter_mut() {
+                    let e2 = 2 * plane.err;
+                    let (x_steps, y_steps) = (e2 >= plane.dy, e2 <= plane.dx);
+                    if x_steps {
+                        plane.err += plane.dy;
+                    }
+                    if y_steps {
+                        plane.err += plane.dx;
+                    }
+                    if (plane.axis_is_x && x_steps) || (!plane.axis_is_x && y_steps) {
+                        coords[plane.axis] += signs[plane.axis];
+                    }
+                }
+                coords[*drive] += signs[*drive];
+            },
+        }
+        Vent::from_coords(coords)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+impl VentLine {
+    /* In i64, as ends at opposite extremes of i32 are further
+     * apart than an i32 can hold.
+     */
+    fn deltas(&self) -> [i64; 3] {
+        let (start, end) = (self.start().coords(), self.end().coords());
+        [0, 1, 2].map(|axis| end[axis] as i64 - start[axis] as i64)
+    }
+
+    /* Along a single axis, which is all part 1 looks at: in the
+     * plane, horizontal or vertical.
+     */
+    pub fn is_axis_aligned(&self) -> bool {
+        self.deltas().iter().filter(|&&d| d != 0).count() <= 1
+    }
+
+    /* Along an axis or at 45 degrees, so either raster draws the
+     * same cells.
+     */
+    pub fn is_octilinear(&self) -> bool {
+        self.point_count(Raster::Lattice) == self.point_count(Raster::Bresenham)
+    }
+
+    /* How many cells points() covers, without walking them. */
+    pub fn point_count(&self, raster: Raster) -> usize {
+        let deltas = self.deltas();
+        match raster {
+            Raster::Lattice => deltas.iter().fold(0, |g, &d| gcd(g, d)) as usize + 1,
+            Raster::Bresenham => deltas.iter().map(|d| d.unsigned_abs()).max().unwrap_or(0) as usize + 1,
+        }
+    }
+
+    /* Every cell the line covers, ends included. */
+    pub fn points(&self, raster: Raster) -> Points {
+        let deltas = self.deltas();
+        let walk = match raster {
+            Raster::Lattice => {
+                let steps = deltas.iter().fold(0, |g, &d| gcd(g, d)).max(1);
+                Walk::Lattice { step: [deltas[0] / steps, deltas[1] / steps, deltas[2] / steps] }
+            },
+            Raster::Bresenham => {
+                let lengths = deltas.map(i64::abs);
+                let drive = (0..3).fold(0, |drive, axis| if lengths[axis] > lengths[drive] { axis } else { drive });
+                let mut others = (0..3).filter(|&axis| axis != drive).map(|axis| {
+                    let (dx, dy) = if axis < drive {
+                        (lengths[axis], -lengths[drive])
+                    } else {
+                        (lengths[drive], -lengths[axis])
+                    };
+                    Plane { axis, axis_is_x: axis < drive, dx, dy, err: dx + dy }
+                });
+                let planes = [others.next().unwrap(), others.next().unwrap()];
+                Walk::Bresenham { drive, signs: deltas.map(|d| d.signum() as i32), planes }
+            },
+        };
+        Points {
+            at: Some(self.start()),
+            end: self.end(),
+            walk,
+        }
+    }
+}
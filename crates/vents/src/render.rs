@@ -0,0 +1,135 @@
+/*
+ * Pictures of a VentsMap: its lines as an SVG drawing, and its
+ * counts as a heatmap image.
+ */
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use util::image::{self, Rgb};
+use util::Grid;
+
+use crate::{Vent, VentLine, VentsMap};
+
+/* Background, and cells with no vents in the heatmap. */
+const EMPTY: Rgb = [0, 0, 0];
+
+/* Size of the longer side of an SVG drawing, in pixels, unless the
+ * map is bigger than that.
+ */
+const SVG_SIZE: i64 = 1000;
+
+/* A rectangle of cells, corners included. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    min: Vent,
+    max: Vent,
+}
+
+impl Region {
+    /* The region between two opposite corners, in either order. */
+    pub fn new(a: Vent, b: Vent) -> Region {
+        Region {
+            min: Vent::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vent::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /* Top left corner. */
+    pub fn min(&self) -> Vent {
+        self.min
+    }
+
+    /* Bottom right corner. */
+    pub fn max(&self) -> Vent {
+        self.max
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x as i64 - self.min.x as i64) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y as i64 - self.min.y as i64) as usize + 1
+    }
+
+    pub fn contains(&self, vent: &Vent) -> bool {
+        (self.min.x..=self.max.x).contains(&vent.x) && (self.min.y..=self.max.y).contains(&vent.y)
+    }
+
+    /* Whether any of a line could be in the region. */
+    fn touches(&self, line: &VentLine) -> bool {
+        line.x1.max(line.x2) >= self.min.x
+            && line.x1.min(line.x2) <= self.max.x
+            && line.y1.max(line.y2) >= self.min.y
+            && line.y1.min(line.y2) <= self.max.y
+    }
+}
+
+/* Parses "x1,y1,x2,y2". */
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(region: &str) -> Result<Region, String> {
+        let coords = region
+            .split(',')
+            .map(|c| c.trim().parse::<i32>().map_err(|e| format!("{}: {:?}", e, c)))
+            .collect::<Result<Vec<i32>, String>>()?;
+        match coords[..] {
+            [x1, y1, x2, y2] => Ok(Region::new(Vent::new(x1, y1), Vent::new(x2, y2))),
+            _ => Err(format!("expected a region like \"0,0,99,99\", not {:?}", region)),
+        }
+    }
+}
+
+impl VentsMap {
+    /* What gets drawn if no region is asked for: all the vents,
+     * or if there aren't any, just the origin.
+     */
+    fn whole_map(&self) -> Region {
+        self.bounds().unwrap_or_else(|| Region::new(Vent::new(0, 0), Vent::new(0, 0)))
+    }
+
+    /* Image with a pixel per cell of the region (or the whole map),
+     * black where there are no vents and otherwise colored by count,
+     * from purple for 1 up to yellow for the most overlapped.  With
+     * depth, it's the view from above, counting every cell below.
+     */
+    pub fn heatmap(&self, region: Option<Region>) -> Grid<Rgb> {
+        let region = region.unwrap_or_else(|| self.whole_map());
+        let mut totals = Grid::new(region.width(), region.height(), 0);
+        for (vent, count) in self.counts.iter().filter(|(vent, _)| region.contains(vent)) {
+            totals[((vent.x - region.min.x) as usize, (vent.y - region.min.y) as usize)] += count;
+        }
+        let most = totals.iter().copied().max().unwrap_or(1);
+        let range = (most - 1).max(1) as f64;
+        totals.map(|&count| match count {
+            0 => EMPTY,
+            count => image::colormap((count - 1) as f64 / range),
+        })
+    }
+
+    /* SVG drawing of each line in the region (or the whole map), in
+     * translucent strokes so overlaps show up brighter.  Cell centers
+     * are at whole-number coordinates; lines with depth are drawn as
+     * seen from above.
+     */
+    pub fn to_svg(&self, region: Option<Region>) -> String {
+        let region = region.unwrap_or_else(|| self.whole_map());
+        let (width, height) = (region.width() as i64, region.height() as i64);
+        let scale = (SVG_SIZE / width.max(height)).max(1);
+        let mut svg = String::new();
+        writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+            width * scale, height * scale,
+            region.min.x as f64 - 0.5, region.min.y as f64 - 0.5, width, height).unwrap();
+        writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"black\"/>",
+            region.min.x as f64 - 0.5, region.min.y as f64 - 0.5, width, height).unwrap();
+        writeln!(svg, "<g stroke=\"#fde725\" stroke-opacity=\"0.5\" stroke-width=\"0.6\" stroke-linecap=\"square\">").unwrap();
+        for line in self.lines.iter().filter(|line| region.touches(line)) {
+            writeln!(svg, "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>", line.x1, line.y1, line.x2, line.y2).unwrap();
+        }
+        writeln!(svg, "</g>").unwrap();
+        writeln!(svg, "</svg>").unwrap();
+        svg
+    }
+}
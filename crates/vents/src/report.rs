@@ -0,0 +1,99 @@
+/*
+ * Where the vents overlap, and which lines make the worst of it.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Vent, VentLine, VentsMap};
+
+/* One of the most covered cells, with the lines covering it in
+ * survey order.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hotspot {
+    pub vent: Vent,
+    pub lines: Vec<VentLine>,
+}
+
+/* How much of a VentsMap is covered how many times. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct OverlapReport {
+    /* Number of cells covered exactly n times, indexed by n, up to
+     * max_coverage.  Cells with no vents aren't counted.
+     */
+    pub histogram: Vec<usize>,
+    pub max_coverage: i32,
+    /* Every cell covered max_coverage times, in reading order, layer
+     * by layer.
+     */
+    pub hotspots: Vec<Hotspot>,
+}
+
+impl OverlapReport {
+    /* Cells covered at least threshold times, as
+     * VentsMap::overlapping_vent_count() counts them.
+     */
+    pub fn at_least(&self, threshold: i32) -> usize {
+        self.histogram.iter().skip(threshold.max(1) as usize).sum()
+    }
+}
+
+impl VentsMap {
+    /* Coverage histogram and hotspots of the map.  Finding which
+     * lines make the hotspots means drawing the lines again.
+     */
+    pub fn report(&self) -> OverlapReport {
+        let mut histogram: Vec<usize> = Vec::new();
+        let mut hottest: Vec<Vent> = Vec::new();
+        let mut max_coverage = 0;
+        for (vent, count) in self.counts.iter() {
+            let count = count as usize;
+            if histogram.len() <= count {
+                histogram.resize(count + 1, 0);
+            }
+            histogram[count] += 1;
+            if count > max_coverage {
+                max_coverage = count;
+                hottest.clear();
+            }
+            if count == max_coverage {
+                hottest.push(vent);
+            }
+        }
+        hottest.sort_unstable_by_key(|vent| (vent.z, vent.y, vent.x));
+
+        let index: HashMap<Vent, usize> = hottest.iter().enumerate().map(|(i, vent)| (*vent, i)).collect();
+        let mut hotspots: Vec<Hotspot> = hottest
+            .into_iter()
+            .map(|vent| Hotspot { vent, lines: Vec::new() })
+            .collect();
+        for ventline in &self.lines {
+            for vent in ventline.points(self.raster) {
+                if let Some(&i) = index.get(&vent) {
+                    hotspots[i].lines.push(*ventline);
+                }
+            }
+        }
+        OverlapReport {
+            histogram,
+            max_coverage: max_coverage as i32,
+            hotspots,
+        }
+    }
+}
+
+impl fmt::Display for OverlapReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "coverage      cells")?;
+        for (coverage, cells) in self.histogram.iter().enumerate().skip(1) {
+            writeln!(f, "{:>8} {:>10}", coverage, cells)?;
+        }
+        writeln!(f, "max coverage {} at {} cells:", self.max_coverage, self.hotspots.len())?;
+        for hotspot in &self.hotspots {
+            let lines: Vec<String> = hotspot.lines.iter().map(VentLine::to_string).collect();
+            writeln!(f, "{:>8}   {}", hotspot.vent.to_string(), lines.join("; "))?;
+        }
+        Ok(())
+    }
+}
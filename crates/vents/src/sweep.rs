@@ -0,0 +1,343 @@
+/*
+ * Counting the cells where vent lines overlap without drawing
+ * them: collinear lines overlap along a stretch of their common
+ * line, and any other two cross in at most one cell.  Only lines
+ * at odd angles under Raster::Bresenham, which cover cells off
+ * their lattice points, have to be drawn.
+ */
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::{Raster, VentLine, VentSurvey};
+
+/* How overlaps are counted. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Engine {
+    /* Draw every line onto a VentsMap and count its cells. */
+    #[default]
+    Raster,
+    /* Work them out from the lines' ends, however long they are,
+     * drawing only lines at odd angles under Raster::Bresenham.  Only
+     * for lines in the plane; surveys with depth are drawn.
+     */
+    Sweep,
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(engine: &str) -> Result<Engine, String> {
+        match engine {
+            "raster" => Ok(Engine::Raster),
+            "sweep" => Ok(Engine::Sweep),
+            _ => Err(format!("unknown engine {:?}", engine)),
+        }
+    }
+}
+
+type Point = (i64, i64);
+
+/* A line with its ends ordered along its direction, which is the
+ * smallest lattice step pointing right (or down, if vertical).  A
+ * single point has no direction.
+ */
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    start: Point,
+    end: Point,
+    step: Point,
+    /* The collinear group it belongs to, unless it's a point. */
+    group: Option<usize>,
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+fn cross(a: Point, b: Point) -> i128 {
+    a.0 as i128 * b.1 as i128 - a.1 as i128 * b.0 as i128
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/* Position of a point along lines going in direction step.  Each
+ * lattice step along a line adds the step's length squared.
+ */
+fn along(point: Point, step: Point) -> i128 {
+    point.0 as i128 * step.0 as i128 + point.1 as i128 * step.1 as i128
+}
+
+impl Segment {
+    fn new(line: &VentLine) -> Segment {
+        let (mut start, mut end) = ((line.x1 as i64, line.y1 as i64), (line.x2 as i64, line.y2 as i64));
+        let (dx, dy) = sub(end, start);
+        let steps = gcd(dx, dy).max(1);
+        let mut step = (dx / steps, dy / steps);
+        if step.0 < 0 || (step.0 == 0 && step.1 < 0) {
+            std::mem::swap(&mut start, &mut end);
+            step = (-step.0, -step.1);
+        }
+        Segment { start, end, step, group: None }
+    }
+
+    fn is_point(&self) -> bool {
+        self.step == (0, 0)
+    }
+
+    /* Identifies the infinite line the segment lies on. */
+    fn carrier(&self) -> (Point, i128) {
+        (self.step, cross(self.step, self.start))
+    }
+
+    fn x_range(&self) -> (i64, i64) {
+        (self.start.0, self.end.0)
+    }
+
+    fn y_range(&self) -> (i64, i64) {
+        (self.start.1.min(self.end.1), self.start.1.max(self.end.1))
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        if self.is_point() {
+            return point == self.start;
+        }
+        let position = along(point, self.step);
+        cross(sub(point, self.start), self.step) == 0
+            && along(self.start, self.step) <= position
+            && position <= along(self.end, self.step)
+    }
+
+    /* The one lattice point where two segments that aren't collinear
+     * meet, if they do.  Any integer point on a segment is one of its
+     * lattice points, as its step is as short as they come.
+     */
+    fn crossing(&self, other: &Segment) -> Option<Point> {
+        if self.is_point() {
+            return Some(self.start).filter(|&p| other.contains(p));
+        }
+        if other.is_point() {
+            return Some(other.start).filter(|&p| self.contains(p));
+        }
+        let (r, s) = (sub(self.end, self.start), sub(other.end, other.start));
+        let mut denom = cross(r, s);
+        if denom == 0 {
+            return None;
+        }
+        let offset = sub(other.start, self.start);
+        let (mut t, mut u) = (cross(offset, s), cross(offset, r));
+        if denom < 0 {
+            denom = -denom;
+            t = -t;
+            u = -u;
+        }
+        if !(0..=denom).contains(&t) || !(0..=denom).contains(&u) {
+            return None;
+        }
+        let (x, y) = (r.0 as i128 * t, r.1 as i128 * t);
+        if x % denom != 0 || y % denom != 0 {
+            return None;
+        }
+        Some((self.start.0 + (x / denom) as i64, self.start.1 + (y / denom) as i64))
+    }
+}
+
+/* Stretches of one line covered by two or more of its segments, as
+ * half-open ranges of positions along it.
+ */
+#[derive(Debug)]
+struct Overlaps {
+    step: Point,
+    ranges: Vec<(i128, i128)>,
+}
+
+impl Overlaps {
+    /* One pass over the segments' ends in order along the line,
+     * keeping count of how many segments are open.
+     */
+    fn new(segments: &[Segment]) -> Overlaps {
+        let step = segments[0].step;
+        let stride = along(step, step);
+        let mut events: Vec<(i128, i32)> = segments
+            .iter()
+            .flat_map(|s| [(along(s.start, step), 1), (along(s.end, step) + stride, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut ranges: Vec<(i128, i128)> = Vec::new();
+        let mut open = 0;
+        let mut since = 0;
+        for (position, change) in events {
+            if open < 2 && open + change >= 2 {
+                since = position;
+            } else if open >= 2 && open + change < 2 && position > since {
+                ranges.push((since, position));
+            }
+            open += change;
+        }
+        Overlaps { step, ranges }
+    }
+
+    fn cells(&self) -> i64 {
+        let stride = along(self.step, self.step);
+        self.ranges.iter().map(|(from, to)| ((to - from) / stride) as i64).sum()
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        let position = along(point, self.step);
+        let i = self.ranges.partition_point(|&(_, to)| to <= position);
+        i < self.ranges.len() && self.ranges[i].0 <= position
+    }
+}
+
+/* How many segments cover a point, looked up on the lines they lie
+ * on rather than drawn.  Each line keeps its segments' ends, as
+ * positions along it, so a lookup is a binary search per direction
+ * the segments go in.
+ */
+struct Coverage {
+    steps: Vec<Point>,
+    lines: HashMap<(Point, i128), (Vec<i128>, Vec<i128>)>,
+    points: HashMap<Point, usize>,
+}
+
+impl Coverage {
+    fn new(segments: &[Segment]) -> Coverage {
+        let mut coverage = Coverage { steps: Vec::new(), lines: HashMap::new(), points: HashMap::new() };
+        for segment in segments {
+            if segment.is_point() {
+                *coverage.points.entry(segment.start).or_insert(0) += 1;
+                continue;
+            }
+            if !coverage.steps.contains(&segment.step) {
+                coverage.steps.push(segment.step);
+            }
+            let (starts, ends) = coverage.lines.entry(segment.carrier()).or_default();
+            starts.push(along(segment.start, segment.step));
+            ends.push(along(segment.end, segment.step));
+        }
+        for (starts, ends) in coverage.lines.values_mut() {
+            starts.sort_unstable();
+            ends.sort_unstable();
+        }
+        coverage
+    }
+
+    /* Segments started at or before the point, less those already
+     * ended before it, on each line through it.
+     */
+    fn at(&self, point: Point) -> usize {
+        let mut count = self.points.get(&point).copied().unwrap_or(0);
+        for &step in &self.steps {
+            if let Some((starts, ends)) = self.lines.get(&(step, cross(step, point))) {
+                let position = along(point, step);
+                count += starts.partition_point(|&s| s <= position) - ends.partition_point(|&e| e < position);
+            }
+        }
+        count
+    }
+}
+
+impl VentSurvey {
+    /* Number of cells covered by two or more lines, the same as a
+     * VentsMap drawn with the survey's raster would find, but without
+     * visiting every cell, so coordinates can run into the millions.
+     *
+     * Lines that only cover lattice points (any line, with
+     * Raster::Lattice, or with Bresenham, those along an axis or at
+     * 45 degrees) are swept.  Bresenham lines at other angles are
+     * drawn, and each of their cells counted if it makes an overlap
+     * the swept lines didn't already.
+     */
+    pub fn sweep_overlap_count(&self, use_diagonals: bool) -> i64 {
+        let (swept, drawn): (Vec<&VentLine>, Vec<&VentLine>) = self.lines
+            .iter()
+            .filter(|ventline| use_diagonals || ventline.is_axis_aligned())
+            .partition(|ventline| self.raster == Raster::Lattice || ventline.is_octilinear());
+        let segments: Vec<Segment> = swept.into_iter().map(Segment::new).collect();
+        let mut count = sweep_segments(segments.clone());
+        if drawn.is_empty() {
+            return count;
+        }
+
+        let mut cells: HashMap<Point, usize> = HashMap::new();
+        for ventline in drawn {
+            for vent in ventline.points(self.raster) {
+                *cells.entry((vent.x() as i64, vent.y() as i64)).or_insert(0) += 1;
+            }
+        }
+        let coverage = Coverage::new(&segments);
+        count += cells
+            .into_iter()
+            .filter(|&(point, drawn)| {
+                let swept = coverage.at(point);
+                swept < 2 && swept + drawn >= 2
+            })
+            .count() as i64;
+        count
+    }
+}
+
+/* Number of lattice points covered by two or more segments.
+ *
+ * Collinear segments are grouped by the line they lie on, and each
+ * group's overlaps found in one pass along it.  Then a sweep from
+ * left to right finds every pair of segments whose x ranges overlap,
+ * for the single points where other segments cross; points in
+ * collinear overlaps aren't counted twice.
+ */
+fn sweep_segments(mut segments: Vec<Segment>) -> i64 {
+    let mut carriers: HashMap<(Point, i128), usize> = HashMap::new();
+    let mut groups: Vec<Vec<Segment>> = Vec::new();
+    for segment in segments.iter_mut().filter(|s| !s.is_point()) {
+        let group = *carriers.entry(segment.carrier()).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        segment.group = Some(group);
+        groups[group].push(*segment);
+    }
+    let overlaps: Vec<Overlaps> = groups.iter().map(|group| Overlaps::new(group)).collect();
+
+    /* Crossing points, with the groups of the lines through them. */
+    let mut crossings: HashMap<Point, Vec<usize>> = HashMap::new();
+    segments.sort_unstable_by_key(|s| s.x_range().0);
+    let mut active: Vec<Segment> = Vec::new();
+    for segment in &segments {
+        let (left, _) = segment.x_range();
+        active.retain(|s| s.x_range().1 >= left);
+        let (top, bottom) = segment.y_range();
+        for other in &active {
+            let (other_top, other_bottom) = other.y_range();
+            if other_bottom < top || other_top > bottom {
+                continue;
+            }
+            if segment.group.is_some() && segment.group == other.group {
+                continue;
+            }
+            if let Some(point) = segment.crossing(other) {
+                let groups = crossings.entry(point).or_default();
+                groups.extend(segment.group);
+                groups.extend(other.group);
+            }
+        }
+        active.push(*segment);
+    }
+
+    /* A crossing point counts once, unless it's in collinear
+     * overlaps already counted; if it's in several (where two
+     * overlaps cross), all but one of those come off again.
+     */
+    let crossing_cells: i64 = crossings
+        .iter_mut()
+        .map(|(&point, groups)| {
+            groups.sort_unstable();
+            groups.dedup();
+            let counted = groups.iter().filter(|&&g| overlaps[g].contains(point)).count() as i64;
+            if counted == 0 { 1 } else { 1 - counted }
+        })
+        .sum();
+    overlaps.iter().map(Overlaps::cells).sum::<i64>() + crossing_cells
+}
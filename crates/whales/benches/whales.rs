@@ -0,0 +1,66 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use whales::SubPositions;
+use util::gen::{self, Rng};
+use util::Solver;
+
+/* The official input is a single line of 1000 crab positions, spread
+ * over roughly 0-2000.
+ */
+fn generate(scale: usize) -> Vec<String> {
+    let mut rng = Rng::new(scale as u64);
+    let crabs: Vec<String> = (0..1000 * scale)
+        .map(|_| {
+            /* Bunch the crabs up toward the low end, like the real input. */
+            let a = rng.below(2000);
+            let b = rng.below(2000);
+            a.min(b).to_string()
+        })
+        .collect();
+    vec![crabs.join(",")]
+}
+
+/* Answers worked out another way, from the median and the mean,
+ * to check the solver gets them right at every scale before timing
+ * it.  Part 2's best position is within half a step of the mean.
+ */
+fn expected(input: &[String]) -> (u64, u64) {
+    let mut crabs: Vec<i64> = input[0].split(',').map(|c| c.parse().unwrap()).collect();
+    crabs.sort_unstable();
+    let median = crabs[crabs.len() / 2];
+    let part1 = crabs.iter().map(|c| (c - median).abs()).sum::<i64>();
+    let mean = crabs.iter().sum::<i64>() / crabs.len() as i64;
+    let part2 = (mean..=mean + 1)
+        .map(|to| crabs.iter().map(|c| (c - to).abs() * ((c - to).abs() + 1) / 2).sum::<i64>())
+        .min()
+        .unwrap();
+    (part1 as u64, part2 as u64)
+}
+
+fn parse(input: &[String]) -> SubPositions {
+    SubPositions::new(&input[0]).unwrap()
+}
+
+fn bench_whales(c: &mut Criterion) {
+    let mut group = c.benchmark_group("whales");
+    group.sample_size(10);
+    for scale in gen::bench_scales() {
+        let input = generate(scale);
+        let solver = parse(&input);
+        assert_eq!((solver.part1(), solver.part2()), expected(&input), "scale {}", scale);
+        group.throughput(Throughput::Bytes(input.iter().map(|l| l.len() as u64 + 1).sum()));
+        group.bench_with_input(BenchmarkId::new("parse", scale), &input, |b, input| {
+            b.iter(|| parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", scale), &solver, |b, solver| {
+            b.iter(|| solver.part1())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", scale), &solver, |b, solver| {
+            b.iter(|| solver.part2())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_whales);
+criterion_main!(benches);
@@ -0,0 +1,138 @@
+/*
+ * https://adventofcode.com/2021/day/7
+ */
+
+use std::io::BufRead;
+
+use util::{ParseError, Solver};
+
+#[derive(Clone, Debug)]
+pub struct CrabSub {
+    position: u32,
+}
+
+#[derive(Debug)]
+pub struct SubPositions {
+    positions: Vec<CrabSub>
+}
+
+impl SubPositions {
+
+    pub fn from_reader<R: BufRead>(input: R) -> Result<SubPositions, ParseError> {
+        let position_string = util::read_lines(input)?;
+        match position_string.first() {
+            Some(line) => SubPositions::new(line).map_err(|e| e.on_line(1, line)),
+            None => Err(ParseError::new("no crab positions in input")),
+        }
+    }
+
+    pub fn new(text: &str) -> Result<SubPositions, ParseError> {
+        let positions: Vec<CrabSub> =
+            text.split(',')
+            .map(|s| Ok(CrabSub { position: util::parse_field(text, s)? }))
+            .collect::<Result<_, ParseError>>()?;
+        // FIXME: need to convert crab locations to how many crabs
+        // FIXME: are at each position.
+        Ok(SubPositions {
+            positions,
+        })
+    }
+
+    pub fn maximum_position(&self) -> u32 {
+        let mut max_position = 0;
+        for sub in &self.positions {
+            if sub.position > max_position {
+                max_position = sub.position;
+            }
+        }
+        max_position
+    }
+
+    /* Fuel adds up in i64: a million crabs a few thousand apart
+     * burn far more than fits in 32 bits.
+     */
+    pub fn find_minimal_fuel_constant_burn(&self) -> u64 {
+        let max_position = self.maximum_position();
+        let mut min_fuel: Option<i64> = None;
+        for i in 0..(max_position + 1) {
+            let mut fuel_cost:i64 = 0;
+            for sub in &self.positions {
+                fuel_cost += (sub.position as i64 - i as i64).abs();
+            }
+            if min_fuel.is_none() || (min_fuel.is_some() && fuel_cost < min_fuel.unwrap()) {
+                min_fuel = Some(fuel_cost);
+            }
+        }
+        min_fuel.unwrap() as u64
+    }
+
+    pub fn find_minimal_fuel_variable_burn(&self) -> u64 {
+        let max_position = self.maximum_position();
+        let mut min_fuel: Option<i64> = None;
+        for i in 0..(max_position + 1) {
+            let mut fuel_cost:i64 = 0;
+            for sub in &self.positions {
+                let distance = (sub.position as i64 - i as i64).abs();
+                let cost = (distance * (distance + 1)) / 2;
+                fuel_cost += cost;
+            }
+            if min_fuel.is_none() || (min_fuel.is_some() && fuel_cost < min_fuel.unwrap()) {
+                min_fuel = Some(fuel_cost);
+            }
+        }
+        min_fuel.unwrap() as u64
+    }
+}
+
+impl Solver for SubPositions {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "The Treachery of Whales";
+    const NAME: &'static str = "whales";
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse<R: BufRead>(input: R) -> Result<Self, ParseError> {
+        SubPositions::from_reader(input)
+    }
+
+    fn part1(&self) -> u64 {
+        self.find_minimal_fuel_constant_burn()
+    }
+
+    fn part2(&self) -> u64 {
+        self.find_minimal_fuel_variable_burn()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{PathBuf};
+    
+    #[test]
+    fn test_whales_part1() {
+        const MINIMUM_FUEL: u64 = 37;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("whales_test.txt");
+        let positions = SubPositions::from_reader(util::open(&input).unwrap()).unwrap();
+        let min_fuel = positions.find_minimal_fuel_constant_burn();
+        assert_eq!(min_fuel, MINIMUM_FUEL);
+    }
+
+    #[test]
+    fn test_whales_part2() {
+        const MINIMUM_FUEL: u64 = 168;
+        let mut input = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        input.push("whales_test.txt");
+        let positions = SubPositions::from_reader(util::open(&input).unwrap()).unwrap();
+        let min_fuel = positions.find_minimal_fuel_variable_burn();
+        assert_eq!(min_fuel, MINIMUM_FUEL);
+    }
+
+    #[test]
+    fn test_whales_bad_position() {
+        let err = SubPositions::new("16,1,-2,0").unwrap_err();
+        assert_eq!(err.column(), 6);
+    }
+}